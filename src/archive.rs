@@ -0,0 +1,178 @@
+use std::io::{Cursor, Read};
+
+use flate2::read::GzDecoder;
+
+use crate::part::Part;
+
+/// Archive formats that can be inspected in part bodies.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+/// A single file entry of an archive.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+impl<'a> Part<'a> {
+    /// Detect the archive format of the part body by its magic bytes.
+    pub fn archive_format(&self) -> Option<ArchiveFormat> {
+        let body = self.body()?;
+        if body.starts_with(b"PK\x03\x04") || body.starts_with(b"PK\x05\x06") {
+            Some(ArchiveFormat::Zip)
+        } else if body.starts_with(&[0x1f, 0x8b]) {
+            Some(ArchiveFormat::TarGz)
+        } else if body.len() > 262 && &body[257..262] == b"ustar" {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// List the file entries of a ZIP, tar or tar.gz part body. Directories are skipped.
+    ///
+    /// Returns `None` if the body is not an archive or cannot be read.
+    pub fn archive_entries(&self) -> Option<Vec<ArchiveEntry>> {
+        let body = self.body()?;
+        match self.archive_format()? {
+            ArchiveFormat::Zip => zip_entries(body),
+            ArchiveFormat::Tar => tar_entries(body),
+            ArchiveFormat::TarGz => tar_entries(GzDecoder::new(body)),
+        }
+    }
+}
+
+fn zip_entries(body: &[u8]) -> Option<Vec<ArchiveEntry>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(body)).ok()?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).ok()?;
+        if file.is_dir() {
+            continue;
+        }
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).ok()?;
+        entries.push(ArchiveEntry {
+            path: file.name().to_string(),
+            content,
+        });
+    }
+    Some(entries)
+}
+
+fn tar_entries<R: Read>(reader: R) -> Option<Vec<ArchiveEntry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = vec![];
+    for entry in archive.entries().ok()? {
+        let mut entry = entry.ok()?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path().ok()?.to_string_lossy().into_owned();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content).ok()?;
+        entries.push(ArchiveEntry { path, content });
+    }
+    Some(entries)
+}
+
+#[cfg(test)]
+pub(crate) mod test_archives {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    pub fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(vec![]));
+        writer.add_directory("data/", zip::write::SimpleFileOptions::default()).unwrap();
+        for (path, content) in files {
+            writer.start_file(*path, zip::write::SimpleFileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    pub fn tar(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(vec![]);
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    pub fn tar_gz(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&tar(files)).unwrap();
+        encoder.finish().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_archives::*;
+    use super::*;
+
+    fn part_with_body(body: &[u8]) -> Vec<u8> {
+        let mut content = b"Content-Disposition: form-data; name=\"export\"\r\n\r\n".to_vec();
+        content.extend_from_slice(body);
+        content
+    }
+
+    fn expected() -> Vec<ArchiveEntry> {
+        vec![
+            ArchiveEntry { path: "data/report.csv".into(), content: b"a,b\n1,2\n".to_vec() },
+            ArchiveEntry { path: "README".into(), content: b"hello".to_vec() },
+        ]
+    }
+
+    const FILES: &[(&str, &[u8])] = &[
+        ("data/report.csv", b"a,b\n1,2\n"),
+        ("README", b"hello"),
+    ];
+
+    #[test]
+    fn should_list_zip_entries() {
+        let content = part_with_body(&zip(FILES));
+        let part = Part::from(content.as_slice());
+
+        assert_eq!(part.archive_format(), Some(ArchiveFormat::Zip));
+        assert_eq!(part.archive_entries(), Some(expected()));
+    }
+
+    #[test]
+    fn should_list_tar_entries() {
+        let content = part_with_body(&tar(FILES));
+        let part = Part::from(content.as_slice());
+
+        assert_eq!(part.archive_format(), Some(ArchiveFormat::Tar));
+        assert_eq!(part.archive_entries(), Some(expected()));
+    }
+
+    #[test]
+    fn should_list_tar_gz_entries() {
+        let content = part_with_body(&tar_gz(FILES));
+        let part = Part::from(content.as_slice());
+
+        assert_eq!(part.archive_format(), Some(ArchiveFormat::TarGz));
+        assert_eq!(part.archive_entries(), Some(expected()));
+    }
+
+    #[test]
+    fn should_return_none_for_non_archives() {
+        let content = part_with_body(b"just some text");
+        let part = Part::from(content.as_slice());
+
+        assert_eq!(part.archive_format(), None);
+        assert_eq!(part.archive_entries(), None);
+    }
+}
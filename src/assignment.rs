@@ -0,0 +1,36 @@
+/// Whether every item can be assigned a different candidate, where `candidates[item]` lists the
+/// indices (below `candidate_count`) that `item` may be assigned to.
+///
+/// Uses augmenting paths, so it stays polynomial even if many items share the same candidates.
+pub(crate) fn assign_all(candidates: &[Vec<usize>], candidate_count: usize) -> bool {
+    let mut assigned_item: Vec<Option<usize>> = vec![None; candidate_count];
+    (0..candidates.len()).all(|item| {
+        let mut visited = vec![false; candidate_count];
+        assign(item, candidates, &mut assigned_item, &mut visited)
+    })
+}
+
+/// Find an augmenting path for `item`, reassigning previously assigned items where necessary.
+fn assign(
+    item: usize,
+    candidates: &[Vec<usize>],
+    assigned_item: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &candidate in &candidates[item] {
+        if visited[candidate] {
+            continue;
+        }
+        visited[candidate] = true;
+
+        let free = match assigned_item[candidate] {
+            None => true,
+            Some(other_item) => assign(other_item, candidates, assigned_item, visited),
+        };
+        if free {
+            assigned_item[candidate] = Some(item);
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,590 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use crate::part::Part;
+use crate::request_utils::{parse_multipart_content_type, split_parts};
+
+/// Builder for multipart bodies as described in RFC 2046 and RFC 7578.
+///
+/// Builds `multipart/form-data` by default; use [`MultipartBuilder::mixed`],
+/// [`MultipartBuilder::related`] or [`MultipartBuilder::with_subtype`] for other kinds. Unless
+/// a boundary is set explicitly, one is generated that doesn't occur in any part.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock_multipart::{MultipartBuilder, PartBuilder, TransferEncoding};
+///
+/// let multipart = MultipartBuilder::new()
+///     .with_boundary("xyz")
+///     .text("title", "Holiday")
+///     .file("photo", "beach.jpg", "image/jpeg", b"jpeg".to_vec())
+///     .part(PartBuilder::form_data("signature")
+///         .with_content_type("application/octet-stream")
+///         .with_transfer_encoding(TransferEncoding::Base64)
+///         .with_body(vec![0xde, 0xad, 0xbe, 0xef]))
+///     .build();
+///
+/// assert_eq!(multipart.content_type, "multipart/form-data; boundary=xyz");
+/// assert!(multipart.body.starts_with(b"--xyz\r\nContent-Disposition: form-data; name=\"title\"\r\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartBuilder {
+    pub subtype: String,
+    pub boundary: Option<String>,
+    pub params: Vec<(String, String)>,
+    pub parts: Vec<PartBuilder>,
+}
+
+/// Builder for a single part of a [`MultipartBuilder`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PartBuilder {
+    pub disposition: Option<String>,
+    pub name: Option<String>,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub transfer_encoding: Option<TransferEncoding>,
+    pub body: PartBody,
+}
+
+/// The body of a [`PartBuilder`]: plain bytes or a nested multipart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartBody {
+    Bytes(Vec<u8>),
+    Multipart(MultipartBuilder),
+}
+
+impl Default for PartBody {
+    fn default() -> Self {
+        PartBody::Bytes(vec![])
+    }
+}
+
+/// A `Content-Transfer-Encoding` applied to a part body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferEncoding {
+    SevenBit,
+    EightBit,
+    Binary,
+    Base64,
+    QuotedPrintable,
+}
+
+/// A built multipart body and the `Content-Type` header to send it with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartBody {
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl Default for MultipartBuilder {
+    fn default() -> Self {
+        MultipartBuilder {
+            subtype: "form-data".to_string(),
+            boundary: None,
+            params: vec![],
+            parts: vec![],
+        }
+    }
+}
+
+impl MultipartBuilder {
+    /// A `multipart/form-data` builder.
+    pub fn new() -> Self { Self::default() }
+
+    /// A `multipart/mixed` builder.
+    pub fn mixed() -> Self {
+        Self::new().with_subtype("mixed")
+    }
+
+    /// A `multipart/related` builder.
+    pub fn related() -> Self {
+        Self::new().with_subtype("related")
+    }
+
+    /// Set the subtype, e.g. `alternative` for `multipart/alternative`.
+    pub fn with_subtype<T: Into<String>>(self, subtype: T) -> Self {
+        MultipartBuilder {
+            subtype: subtype.into(),
+            ..self
+        }
+    }
+
+    /// Use the given boundary instead of generating one. The boundary must not occur in any part.
+    pub fn with_boundary<T: Into<String>>(self, boundary: T) -> Self {
+        MultipartBuilder {
+            boundary: Some(boundary.into()),
+            ..self
+        }
+    }
+
+    /// Add a `Content-Type` parameter, e.g. `type` or `start` for `multipart/related`.
+    pub fn with_param<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Add a text field.
+    pub fn text<N: Into<String>, V: Into<String>>(self, name: N, value: V) -> Self {
+        self.part(PartBuilder::form_data(name).with_body(value.into()))
+    }
+
+    /// Add a file field.
+    pub fn file<N, F, C, B>(self, name: N, filename: F, content_type: C, body: B) -> Self
+        where N: Into<String>,
+              F: Into<String>,
+              C: Into<String>,
+              B: Into<Vec<u8>>,
+    {
+        self.part(PartBuilder::form_data(name)
+            .with_filename(filename)
+            .with_content_type(content_type)
+            .with_body(body))
+    }
+
+    pub fn part(mut self, part: PartBuilder) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Encode the parts and generate a boundary if none was given.
+    pub fn build(&self) -> MultipartBody {
+        let parts = self.parts.iter()
+            .map(PartBuilder::encode)
+            .collect::<Vec<_>>();
+
+        let boundary = self.boundary.clone()
+            .unwrap_or_else(|| generate_boundary(&parts));
+
+        let mut body = vec![];
+        for part in &parts {
+            body.extend_from_slice(b"--");
+            body.extend_from_slice(boundary.as_bytes());
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(part);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(b"--");
+        body.extend_from_slice(boundary.as_bytes());
+        body.extend_from_slice(b"--\r\n");
+
+        let mut content_type = format!("multipart/{}; boundary={}", self.subtype, quote(&boundary));
+        for (name, value) in &self.params {
+            content_type.push_str(&format!("; {}={}", name, quote(value)));
+        }
+
+        MultipartBody {
+            content_type,
+            body,
+        }
+    }
+}
+
+impl MultipartBody {
+    /// Parse the body back into its parts, using the boundary of the content type.
+    pub fn parts(&self) -> Vec<Part<'_>> {
+        parse_multipart_content_type(&self.content_type)
+            .and_then(|content_type| content_type.boundary)
+            .map(|boundary| split_parts(&self.body, boundary))
+            .unwrap_or_default()
+    }
+}
+
+impl PartBuilder {
+    pub fn new() -> Self { Self::default() }
+
+    /// A part with a `Content-Disposition: form-data` header for the given field name.
+    pub fn form_data<T: Into<String>>(name: T) -> Self {
+        PartBuilder::new()
+            .with_disposition("form-data")
+            .with_name(name)
+    }
+
+    /// Set the `Content-Disposition` type, e.g. `form-data`, `attachment` or `inline`.
+    pub fn with_disposition<T: Into<String>>(self, disposition: T) -> Self {
+        PartBuilder {
+            disposition: Some(disposition.into()),
+            ..self
+        }
+    }
+
+    /// Set the `name` parameter of the `Content-Disposition` header. `"`, CR and LF are
+    /// percent-encoded like browsers do.
+    pub fn with_name<T: Into<String>>(self, name: T) -> Self {
+        PartBuilder {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Set the `filename` parameter of the `Content-Disposition` header, encoded like the name.
+    pub fn with_filename<T: Into<String>>(self, filename: T) -> Self {
+        PartBuilder {
+            filename: Some(filename.into()),
+            ..self
+        }
+    }
+
+    pub fn with_content_type<T: Into<String>>(self, content_type: T) -> Self {
+        PartBuilder {
+            content_type: Some(content_type.into()),
+            ..self
+        }
+    }
+
+    /// Add a header. Headers are written in the order they were added, after
+    /// `Content-Disposition` and `Content-Type`.
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Encode the body and add a matching `Content-Transfer-Encoding` header.
+    pub fn with_transfer_encoding(self, transfer_encoding: TransferEncoding) -> Self {
+        PartBuilder {
+            transfer_encoding: Some(transfer_encoding),
+            ..self
+        }
+    }
+
+    pub fn with_body<T: Into<Vec<u8>>>(self, body: T) -> Self {
+        PartBuilder {
+            body: PartBody::Bytes(body.into()),
+            ..self
+        }
+    }
+
+    /// Use a nested multipart as the body. Its `Content-Type` replaces the content type of
+    /// this part.
+    pub fn with_multipart(self, multipart: MultipartBuilder) -> Self {
+        PartBuilder {
+            body: PartBody::Multipart(multipart),
+            ..self
+        }
+    }
+
+    /// Headers and encoded body of the part, without the surrounding delimiters.
+    fn encode(&self) -> Vec<u8> {
+        let (content_type, body) = match &self.body {
+            PartBody::Bytes(bytes) => (self.content_type.clone(), bytes.clone()),
+            PartBody::Multipart(multipart) => {
+                let multipart = multipart.build();
+                (Some(multipart.content_type), multipart.body)
+            },
+        };
+
+        let mut headers = vec![];
+        if self.disposition.is_some() || self.name.is_some() || self.filename.is_some() {
+            let mut disposition = self.disposition.clone().unwrap_or_else(|| "form-data".to_string());
+            if let Some(name) = &self.name {
+                disposition.push_str(&format!("; name=\"{}\"", encode_param(name)));
+            }
+            if let Some(filename) = &self.filename {
+                disposition.push_str(&format!("; filename=\"{}\"", encode_param(filename)));
+            }
+            headers.push(("Content-Disposition".to_string(), disposition));
+        }
+        if let Some(content_type) = content_type {
+            headers.push(("Content-Type".to_string(), content_type));
+        }
+        headers.extend(self.headers.iter().cloned());
+        if let Some(transfer_encoding) = self.transfer_encoding {
+            headers.push(("Content-Transfer-Encoding".to_string(), transfer_encoding.name().to_string()));
+        }
+
+        let mut encoded = vec![];
+        for (name, value) in headers {
+            encoded.extend_from_slice(format!("{}: {}\r\n", name, value).as_bytes());
+        }
+        encoded.extend_from_slice(b"\r\n");
+        match self.transfer_encoding {
+            Some(transfer_encoding) => encoded.extend_from_slice(&transfer_encoding.encode(&body)),
+            None => encoded.extend_from_slice(&body),
+        }
+        encoded
+    }
+}
+
+impl TransferEncoding {
+    /// The value of the `Content-Transfer-Encoding` header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransferEncoding::SevenBit => "7bit",
+            TransferEncoding::EightBit => "8bit",
+            TransferEncoding::Binary => "binary",
+            TransferEncoding::Base64 => "base64",
+            TransferEncoding::QuotedPrintable => "quoted-printable",
+        }
+    }
+
+    /// Encode `body`. `7bit`, `8bit` and `binary` leave it unchanged.
+    pub fn encode(&self, body: &[u8]) -> Vec<u8> {
+        match self {
+            TransferEncoding::SevenBit | TransferEncoding::EightBit | TransferEncoding::Binary => body.to_vec(),
+            TransferEncoding::Base64 => encode_base64(body)
+                .chunks(76)
+                .collect::<Vec<_>>()
+                .join(&b"\r\n"[..]),
+            TransferEncoding::QuotedPrintable => encode_quoted_printable(body),
+        }
+    }
+}
+
+/// Standard base64 with padding (RFC 4648). Encoding is all the builder needs, so it doesn't
+/// depend on the `base64` crate that only the `fixtures` and `mappings` features use.
+fn encode_base64(body: &[u8]) -> Vec<u8> {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = Vec::with_capacity(body.len().div_ceil(3) * 4);
+    for chunk in body.chunks(3) {
+        let bits = chunk.iter()
+            .enumerate()
+            .fold(0u32, |bits, (index, byte)| bits | u32::from(*byte) << (16 - 8 * index));
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize]);
+            } else {
+                encoded.push(b'=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Quoted-printable encoding as described in RFC 2045, keeping CRLF line breaks and wrapping
+/// lines at 76 characters.
+///
+/// Bare CR and LF bytes are encoded as `=0D` and `=0A`, so binary bodies decode to the same
+/// bytes (RFC 2045 §6.7).
+fn encode_quoted_printable(body: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![];
+    let mut line_length = 0;
+    let mut index = 0;
+    while index < body.len() {
+        let byte = body[index];
+        if body[index..].starts_with(b"\r\n") {
+            encoded.extend_from_slice(b"\r\n");
+            line_length = 0;
+            index += 2;
+            continue;
+        }
+
+        let at_line_end = matches!(&body[index + 1..], [] | [b'\r', b'\n', ..]);
+        let literal = (byte == b' ' || byte == b'\t') && !at_line_end
+            || (33..=126).contains(&byte) && byte != b'=';
+        let chunk = if literal {
+            vec![byte]
+        } else {
+            format!("={:02X}", byte).into_bytes()
+        };
+
+        if line_length + chunk.len() > 75 {
+            encoded.extend_from_slice(b"=\r\n");
+            line_length = 0;
+        }
+        line_length += chunk.len();
+        encoded.extend_from_slice(&chunk);
+        index += 1;
+    }
+    encoded
+}
+
+/// Percent-encode `"`, CR and LF the way browsers do for names and filenames.
+fn encode_param(value: &str) -> String {
+    value.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn quote(value: &str) -> String {
+    let token = !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte));
+    if token {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// Generate a boundary that doesn't occur in any of the encoded parts.
+fn generate_boundary(parts: &[Vec<u8>]) -> String {
+    let state = RandomState::new();
+    (0u64..)
+        .map(|attempt| {
+            let mut hasher = state.build_hasher();
+            hasher.write_u64(attempt);
+            format!("wiremock-multipart-{:016x}", hasher.finish())
+        })
+        .find(|boundary| {
+            parts.iter().all(|part| !part.windows(boundary.len()).any(|window| window == boundary.as_bytes()))
+        })
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use wiremock::http::{HeaderName, HeaderValue};
+    use wiremock::Request;
+
+    use crate::request_utils::RequestUtils;
+    use crate::test_utils::requestb;
+
+    use super::*;
+
+    fn request(multipart: MultipartBody) -> Request {
+        let headers: HashMap<HeaderName, HeaderValue> = [(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_str(&multipart.content_type).unwrap(),
+        )].into();
+        requestb(headers, multipart.body)
+    }
+
+    #[test]
+    fn should_build_form() {
+        let multipart = MultipartBuilder::new()
+            .with_boundary("xyz")
+            .text("title", "Holiday")
+            .file("photo", "beach.jpg", "image/jpeg", b"jpeg".to_vec())
+            .build();
+
+        assert_eq!(multipart.content_type, "multipart/form-data; boundary=xyz");
+        assert_eq!(
+            String::from_utf8(multipart.body).unwrap(),
+            "--xyz\r\n\
+             Content-Disposition: form-data; name=\"title\"\r\n\
+             \r\n\
+             Holiday\r\n\
+             --xyz\r\n\
+             Content-Disposition: form-data; name=\"photo\"; filename=\"beach.jpg\"\r\n\
+             Content-Type: image/jpeg\r\n\
+             \r\n\
+             jpeg\r\n\
+             --xyz--\r\n",
+        );
+    }
+
+    #[test]
+    fn should_round_trip_through_parser() {
+        let request = request(MultipartBuilder::new()
+            .text("say \"hi\"", "hello\r\nworld")
+            .part(PartBuilder::form_data("empty"))
+            .part(PartBuilder::new().with_header("X-Custom", "1").with_body("no disposition"))
+            .build());
+        let parts = request.parts();
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].decoded_name().as_deref(), Some("say \"hi\""));
+        assert_eq!(parts[0].body(), Some("hello\r\nworld".as_bytes()));
+        assert_eq!(parts[1].body(), Some("".as_bytes()));
+        assert_eq!(parts[2].header_value("x-custom"), Some("1"));
+        assert_eq!(parts[2].body(), Some("no disposition".as_bytes()));
+    }
+
+    #[test]
+    fn should_generate_boundary_not_in_parts() {
+        let first = MultipartBuilder::new().text("a", "b").build();
+        let boundary = first.content_type.split_once("boundary=").unwrap().1.to_string();
+        let second = MultipartBuilder::new().text("a", format!("--{}", boundary)).build();
+
+        assert!(boundary.starts_with("wiremock-multipart-"));
+        assert_eq!(request(second).parts()[0].body(), Some(format!("--{}", boundary).as_bytes()));
+    }
+
+    #[test]
+    fn should_quote_boundary_if_needed() {
+        let multipart = MultipartBuilder::mixed().with_boundary("----=_Part_0").text("a", "b").build();
+
+        assert_eq!(multipart.content_type, "multipart/mixed; boundary=\"----=_Part_0\"");
+        assert_eq!(multipart.parts()[0].body(), Some("b".as_bytes()));
+    }
+
+    #[test]
+    fn should_build_nested_multipart() {
+        let request = request(MultipartBuilder::new()
+            .text("submitter", "Larry")
+            .part(PartBuilder::form_data("files").with_multipart(MultipartBuilder::mixed()
+                .part(PartBuilder::new().with_disposition("file").with_filename("file1.txt").with_body("one"))
+                .part(PartBuilder::new().with_disposition("file").with_filename("file2.gif").with_body("two"))))
+            .build());
+        let parts = request.parts();
+
+        assert!(parts[1].content_type().unwrap().starts_with("multipart/mixed; boundary="));
+        let nested = parts[1].nested_parts();
+        assert_eq!(nested.len(), 2);
+        assert_eq!(nested[0].disposition_type(), Some("file"));
+        assert_eq!(nested[1].filename(), Some("file2.gif"));
+        assert_eq!(nested[1].body(), Some("two".as_bytes()));
+    }
+
+    #[test]
+    fn should_add_content_type_params() {
+        let multipart = MultipartBuilder::related()
+            .with_boundary("xyz")
+            .with_param("type", "application/json")
+            .with_param("start", "<root>")
+            .build();
+
+        assert_eq!(multipart.content_type, "multipart/related; boundary=xyz; type=\"application/json\"; start=\"<root>\"");
+        assert_eq!(multipart.body, b"--xyz--\r\n");
+    }
+
+    #[test]
+    fn should_apply_transfer_encodings() {
+        let part = PartBuilder::new()
+            .with_transfer_encoding(TransferEncoding::Base64)
+            .with_body(vec![0u8; 60])
+            .encode();
+        assert_eq!(
+            String::from_utf8(part).unwrap(),
+            format!("Content-Transfer-Encoding: base64\r\n\r\n{}\r\n{}", "A".repeat(76), "A".repeat(4)),
+        );
+
+        assert_eq!(TransferEncoding::QuotedPrintable.encode("grüße = 1 \r\nok".as_bytes()), b"gr=C3=BC=C3=9Fe =3D 1=20\r\nok");
+        assert_eq!(TransferEncoding::QuotedPrintable.encode(&[b'a'; 80]), [&[b'a'; 75][..], b"=\r\n", &[b'a'; 5][..]].concat());
+        assert_eq!(TransferEncoding::Binary.encode(&[0xff]), vec![0xff]);
+    }
+
+    #[test]
+    fn should_encode_base64() {
+        for (body, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(TransferEncoding::Base64.encode(body.as_bytes()), expected.as_bytes());
+        }
+        assert_eq!(TransferEncoding::Base64.encode(&[0xfb, 0xff, 0xbf]), b"+/+/");
+    }
+
+    #[test]
+    fn should_keep_binary_bytes_in_quoted_printable() {
+        let decode = |encoded: &[u8]| {
+            let mut decoded = vec![];
+            let mut index = 0;
+            while index < encoded.len() {
+                match &encoded[index..] {
+                    [b'=', b'\r', b'\n', ..] => index += 3,
+                    [b'=', high, low, ..] => {
+                        decoded.push(u8::from_str_radix(std::str::from_utf8(&[*high, *low]).unwrap(), 16).unwrap());
+                        index += 3;
+                    },
+                    [byte, ..] => {
+                        decoded.push(*byte);
+                        index += 1;
+                    },
+                    [] => unreachable!(),
+                }
+            }
+            decoded
+        };
+        let binary = (0..=255u8).chain([b'\n', b' ', b'\r', b'\r', b'\n', b'\t', b'\n']).cycle().take(1000).collect::<Vec<_>>();
+
+        assert_eq!(TransferEncoding::QuotedPrintable.encode(b"a\nb\rc"), b"a=0Ab=0Dc");
+        assert_eq!(decode(&TransferEncoding::QuotedPrintable.encode(&binary)), binary);
+        assert_eq!(decode(&TransferEncoding::QuotedPrintable.encode("line 1 \r\nline 2".as_bytes())), b"line 1 \r\nline 2");
+    }
+}
@@ -0,0 +1,347 @@
+use std::path::Path;
+
+use wiremock::{Request, Respond, ResponseTemplate};
+
+use crate::builder::{MultipartBuilder, PartBuilder};
+
+/// Responder that serves a byte buffer and honours the `Range` header of the request.
+///
+/// Requests without a valid `bytes` range get the whole content with status 200. Malformed
+/// ranges in a list are skipped, and overlapping or adjacent ranges are merged; the others are
+/// served in the order they were requested. A single
+/// satisfiable range is answered with `206 Partial Content` and a `Content-Range` header,
+/// several with a `multipart/byteranges` body that has a `Content-Range` header per part.
+/// If no range is satisfiable the response is `416 Range Not Satisfiable`.
+///
+/// With [`ByteRangesResponder::with_etag`], responses carry an `ETag` header and a request
+/// with `If-Range` only gets a partial response if it names that ETag; otherwise, and always
+/// without an ETag, `If-Range` requests get the whole content, as if the content had changed.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock};
+/// use wiremock::matchers::{method, path};
+/// use wiremock_multipart::ByteRangesResponder;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("GET"))
+///         .and(path("/video.mp4"))
+///         .respond_with(ByteRangesResponder::new(vec![0u8; 4096]).with_content_type("video/mp4"))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteRangesResponder {
+    pub content: Vec<u8>,
+    pub content_type: String,
+    /// The opaque tag of the strong `ETag` validator, without quotes.
+    pub etag: Option<String>,
+}
+
+impl ByteRangesResponder {
+    /// Serve `content` as `application/octet-stream`.
+    pub fn new<T: Into<Vec<u8>>>(content: T) -> Self {
+        ByteRangesResponder {
+            content: content.into(),
+            content_type: "application/octet-stream".to_string(),
+            etag: None,
+        }
+    }
+
+    /// Serve the content of a file, read once when creating the responder.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        std::fs::read(path).map(Self::new)
+    }
+
+    pub fn with_content_type<T: Into<String>>(self, content_type: T) -> Self {
+        ByteRangesResponder {
+            content_type: content_type.into(),
+            ..self
+        }
+    }
+
+    /// Send an `ETag` header with the given opaque tag, e.g. `v1` for `ETag: "v1"`, and honour
+    /// `If-Range` requests that name it.
+    pub fn with_etag<T: Into<String>>(self, etag: T) -> Self {
+        ByteRangesResponder {
+            etag: Some(etag.into()),
+            ..self
+        }
+    }
+
+    /// The satisfiable ranges of a `Range` header as inclusive `(first, last)` pairs, in request
+    /// order. Overlapping or adjacent ranges are merged into the position of the first of them.
+    /// `None` if the header is not a `bytes` range or contains no well-formed range, in which
+    /// case it has to be ignored.
+    fn ranges(&self, range: &str) -> Option<Vec<(u64, u64)>> {
+        let (unit, ranges) = range.trim().split_once('=')?;
+        if !unit.trim().eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+
+        let ranges = ranges.split(',')
+            .filter_map(|range| self.range(range))
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            return None;
+        }
+
+        let mut merged: Vec<(u64, u64)> = vec![];
+        for (mut first, mut last) in ranges.into_iter().flatten() {
+            let mut position = None;
+            let mut index = 0;
+            while index < merged.len() {
+                let (other_first, other_last) = merged[index];
+                if first <= other_last.saturating_add(1) && other_first <= last.saturating_add(1) {
+                    first = first.min(other_first);
+                    last = last.max(other_last);
+                    merged.remove(index);
+                    position.get_or_insert(index);
+                } else {
+                    index += 1;
+                }
+            }
+            merged.insert(position.unwrap_or(merged.len()), (first, last));
+        }
+        Some(merged)
+    }
+
+    /// A single range of a `Range` header: `None` if it is malformed, `Some(None)` if it is not
+    /// satisfiable.
+    fn range(&self, range: &str) -> Option<Option<(u64, u64)>> {
+        let length = self.content.len() as u64;
+        let (first, last) = range.trim().split_once('-')?;
+        match (first.trim(), last.trim()) {
+            ("", suffix) => {
+                let suffix = suffix.parse::<u64>().ok()?;
+                Some((suffix > 0 && length > 0).then(|| (length.saturating_sub(suffix), length - 1)))
+            },
+            (first, last) => {
+                let first = first.parse::<u64>().ok()?;
+                let last = match last {
+                    "" => u64::MAX,
+                    last => last.parse::<u64>().ok()?,
+                };
+                if last < first {
+                    return None;
+                }
+                Some((first < length).then(|| (first, last.min(length - 1))))
+            },
+        }
+    }
+
+    /// Whether the `If-Range` header of the request, if any, names the ETag of the content.
+    /// Dates are not supported and never match.
+    fn if_range_matches(&self, request: &Request) -> bool {
+        match request.headers.get("if-range") {
+            None => true,
+            Some(if_range) => match (&self.etag, if_range.to_str()) {
+                (Some(etag), Ok(if_range)) => if_range.trim() == format!("\"{}\"", etag),
+                _ => false,
+            },
+        }
+    }
+
+    fn template(&self, status: u16) -> ResponseTemplate {
+        match &self.etag {
+            Some(etag) => ResponseTemplate::new(status).insert_header("etag", format!("\"{}\"", etag).as_str()),
+            None => ResponseTemplate::new(status),
+        }
+    }
+
+    fn content_range(&self, (first, last): (u64, u64)) -> String {
+        format!("bytes {}-{}/{}", first, last, self.content.len())
+    }
+
+    fn slice(&self, (first, last): (u64, u64)) -> &[u8] {
+        &self.content[first as usize..=last as usize]
+    }
+}
+
+impl Respond for ByteRangesResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let ranges = request.headers
+            .get("range")
+            .filter(|_| self.if_range_matches(request))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|range| self.ranges(range));
+
+        match ranges.as_deref() {
+            None => self.template(200)
+                .insert_header("accept-ranges", "bytes")
+                .set_body_raw(self.content.clone(), &self.content_type),
+            Some([]) => self.template(416)
+                .insert_header("content-range", format!("bytes */{}", self.content.len()).as_str()),
+            Some([range]) => self.template(206)
+                .insert_header("content-range", self.content_range(*range).as_str())
+                .set_body_raw(self.slice(*range).to_vec(), &self.content_type),
+            Some(ranges) => {
+                let multipart = ranges.iter()
+                    .fold(MultipartBuilder::new().with_subtype("byteranges"), |multipart, range| {
+                        multipart.part(PartBuilder::new()
+                            .with_content_type(self.content_type.as_str())
+                            .with_header("Content-Range", self.content_range(*range))
+                            .with_body(self.slice(*range)))
+                    })
+                    .build();
+                self.template(206)
+                    .set_body_raw(multipart.body, &multipart.content_type)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{Mock, MockServer};
+    use wiremock::matchers::method;
+
+    use crate::builder::MultipartBody;
+    use crate::content_range::ContentRange;
+    use crate::test_utils::{get, header};
+
+    use super::*;
+
+    async fn server() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ByteRangesResponder::new("0123456789").with_content_type("text/plain"))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[async_std::test]
+    async fn should_serve_whole_content_without_range() {
+        let server = server().await;
+
+        for headers in [&[][..], &[("Range", "lines=1-2")][..], &[("Range", "bytes=5-2")][..], &[("Range", "bytes=a-b, 5-2")][..]] {
+            let (status, response_headers, body) = get(&server, headers);
+            assert_eq!(status, 200);
+            assert_eq!(header(&response_headers, "accept-ranges"), Some("bytes"));
+            assert_eq!(body, b"0123456789");
+        }
+    }
+
+    #[async_std::test]
+    async fn should_serve_single_range() {
+        let server = server().await;
+
+        for (range, content_range, expected) in [
+            ("bytes=2-4", "bytes 2-4/10", "234"),
+            ("bytes=7-", "bytes 7-9/10", "789"),
+            ("bytes=-3", "bytes 7-9/10", "789"),
+            ("bytes=8-100", "bytes 8-9/10", "89"),
+            ("bytes=2-4, 20-30", "bytes 2-4/10", "234"),
+            ("bytes=2-4, x-y, 7-3", "bytes 2-4/10", "234"),
+            ("bytes=2-4, 3-6", "bytes 2-6/10", "23456"),
+            ("bytes=5-6, 2-4", "bytes 2-6/10", "23456"),
+            ("bytes=0-1, -9", "bytes 0-9/10", "0123456789"),
+        ] {
+            let (status, headers, body) = get(&server, &[("Range", range)]);
+            assert_eq!(status, 206);
+            assert_eq!(header(&headers, "content-range"), Some(content_range));
+            assert_eq!(header(&headers, "content-type"), Some("text/plain"));
+            assert_eq!(body, expected.as_bytes());
+        }
+    }
+
+    #[async_std::test]
+    async fn should_serve_multiple_ranges_as_byteranges() {
+        let server = server().await;
+
+        let (status, headers, body) = get(&server, &[("Range", "bytes=0-1,5-6, -1")]);
+        let content_type = header(&headers, "content-type").unwrap().to_string();
+        assert_eq!(status, 206);
+        assert!(content_type.starts_with("multipart/byteranges; boundary="));
+
+        let multipart = MultipartBody { content_type, body };
+        let parts = multipart.parts();
+        assert_eq!(
+            parts.iter().map(|part| (part.content_range().unwrap(), part.body().unwrap())).collect::<Vec<_>>(),
+            vec![
+                (ContentRange { first: 0, last: 1, complete_length: Some(10) }, "01".as_bytes()),
+                (ContentRange { first: 5, last: 6, complete_length: Some(10) }, "56".as_bytes()),
+                (ContentRange { first: 9, last: 9, complete_length: Some(10) }, "9".as_bytes()),
+            ],
+        );
+        assert!(parts.iter().all(|part| part.content_type() == Some("text/plain")));
+    }
+
+    #[async_std::test]
+    async fn should_reject_unsatisfiable_ranges() {
+        let server = server().await;
+
+        let (status, headers, _) = get(&server, &[("Range", "bytes=10-20,-0")]);
+        assert_eq!(status, 416);
+        assert_eq!(header(&headers, "content-range"), Some("bytes */10"));
+    }
+
+    #[async_std::test]
+    async fn should_keep_request_order_of_merged_ranges() {
+        let server = server().await;
+
+        let (status, headers, body) = get(&server, &[("Range", "bytes=8-, 0-1, 1-2")]);
+        let content_type = header(&headers, "content-type").unwrap().to_string();
+        assert_eq!(status, 206);
+
+        let multipart = MultipartBody { content_type, body };
+        assert_eq!(
+            multipart.parts().iter().map(|part| part.content_range().unwrap()).collect::<Vec<_>>(),
+            vec![
+                ContentRange { first: 8, last: 9, complete_length: Some(10) },
+                ContentRange { first: 0, last: 2, complete_length: Some(10) },
+            ],
+        );
+    }
+
+    #[async_std::test]
+    async fn should_keep_request_order_of_separate_ranges() {
+        let server = server().await;
+
+        let (_, headers, body) = get(&server, &[("Range", "bytes=7-8, 4-5, 0-1")]);
+        let content_type = header(&headers, "content-type").unwrap().to_string();
+
+        let multipart = MultipartBody { content_type, body };
+        assert_eq!(
+            multipart.parts().iter().map(|part| part.body().unwrap()).collect::<Vec<_>>(),
+            vec!["78".as_bytes(), "45".as_bytes(), "01".as_bytes()],
+        );
+    }
+
+    #[async_std::test]
+    async fn should_honour_if_range_with_matching_etag() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ByteRangesResponder::new("0123456789").with_etag("v1"))
+            .mount(&server)
+            .await;
+
+        let (status, headers, body) = get(&server, &[("Range", "bytes=2-4"), ("If-Range", "\"v1\"")]);
+        assert_eq!(status, 206);
+        assert_eq!(header(&headers, "etag"), Some("\"v1\""));
+        assert_eq!(body, b"234");
+
+        for if_range in ["\"v2\"", "W/\"v1\"", "Wed, 21 Oct 2015 07:28:00 GMT"] {
+            let (status, headers, body) = get(&server, &[("Range", "bytes=2-4"), ("If-Range", if_range)]);
+            assert_eq!(status, 200);
+            assert_eq!(header(&headers, "etag"), Some("\"v1\""));
+            assert_eq!(body, b"0123456789");
+        }
+    }
+
+    #[async_std::test]
+    async fn should_ignore_range_with_if_range_but_without_etag() {
+        let server = server().await;
+
+        let (status, headers, body) = get(&server, &[("Range", "bytes=2-4"), ("If-Range", "\"v1\"")]);
+        assert_eq!(status, 200);
+        assert_eq!(header(&headers, "etag"), None);
+        assert_eq!(body, b"0123456789");
+    }
+}
@@ -0,0 +1,49 @@
+/// The value of a `Content-Range` header, e.g. `bytes 0-99/1000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    pub first: u64,
+    pub last: u64,
+    /// The length of the whole content, `None` if it is unknown (`*`).
+    pub complete_length: Option<u64>,
+}
+
+impl ContentRange {
+    /// Parse a `Content-Range` header value. Returns `None` for values that don't describe a
+    /// range, including `bytes */1000` as sent with status 416.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (unit, range) = value.trim().split_once(' ')?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+
+        let (range, complete_length) = range.trim().split_once('/')?;
+        let (first, last) = range.split_once('-')?;
+        let content_range = ContentRange {
+            first: first.parse().ok()?,
+            last: last.parse().ok()?,
+            complete_length: match complete_length {
+                "*" => None,
+                length => Some(length.parse().ok()?),
+            },
+        };
+
+        let valid = content_range.first <= content_range.last
+            && content_range.complete_length.map(|length| content_range.last < length).unwrap_or(true);
+        valid.then_some(content_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_content_range() {
+        assert_eq!(ContentRange::parse("bytes 0-99/1000"), Some(ContentRange { first: 0, last: 99, complete_length: Some(1000) }));
+        assert_eq!(ContentRange::parse("bytes 5-5/*"), Some(ContentRange { first: 5, last: 5, complete_length: None }));
+        assert_eq!(ContentRange::parse("bytes */1000"), None);
+        assert_eq!(ContentRange::parse("bytes 9-5/1000"), None);
+        assert_eq!(ContentRange::parse("bytes 0-1000/1000"), None);
+        assert_eq!(ContentRange::parse("lines 0-1/2"), None);
+    }
+}
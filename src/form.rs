@@ -0,0 +1,1271 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{
+    self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleStruct,
+};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::part::Part;
+
+/// A file part deserialized from a multipart form.
+///
+/// Use it as the type of struct fields that receive file uploads when calling
+/// [`RequestUtils::form`](crate::RequestUtils::form), or that describe expected file uploads in
+/// [`FormEquals`](crate::matchers::FormEquals).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UploadedFile {
+    pub filename: String,
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+const UPLOADED_FILE: &str = "UploadedFile";
+
+/// Error returned when a multipart form cannot be (de)serialized.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FormError {
+    message: String,
+    /// The field that was reported missing, to retry with absent sequences defaulted to empty.
+    missing_field: Option<String>,
+}
+
+impl FormError {
+    fn new<T: Into<String>>(message: T) -> Self {
+        FormError { message: message.into(), missing_field: None }
+    }
+
+    fn missing(field: &str) -> Self {
+        FormError {
+            message: format!("missing field `{}`", field),
+            missing_field: Some(field.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for FormError {}
+
+impl de::Error for FormError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        FormError::new(msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        FormError::missing(field)
+    }
+}
+
+/// Deserialize the parts of a multipart form into `T`.
+///
+/// Parts are grouped by name. Text parts are decoded with their charset or the form's
+/// `_charset_` field, like [`Part::text_with_fallback`], and parsed into strings, numbers,
+/// booleans, chars and unit enum variants; file parts are deserialized as [`UploadedFile`].
+/// Repeated names can be collected into sequences such as `Vec<T>`, absent names become `None`
+/// for `Option<T>` fields and empty sequences for sequence fields.
+pub(crate) fn from_parts<T: DeserializeOwned>(parts: &[Part]) -> Result<T, FormError> {
+    let form_charset = Part::form_charset(parts);
+    let groups = Part::group_by_name(parts);
+    let fields = groups.iter()
+        .map(|(name, group)| {
+            let values = group.iter()
+                .map(|part| Value::from_part(name, part, fallback_charset(name, form_charset)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((name.as_ref(), values))
+        })
+        .collect::<Result<Vec<_>, FormError>>()?;
+
+    // Serde only defaults absent `Option`s, so fields reported missing are retried as present
+    // without values, which deserializes sequences as empty and still fails for other types.
+    let mut absent: Vec<String> = vec![];
+    loop {
+        let deserializer = FormDeserializer { fields: fields.clone(), absent: &absent };
+        match T::deserialize(deserializer) {
+            Err(FormError { missing_field: Some(name), .. })
+                if !absent.contains(&name) && !fields.iter().any(|(field, _)| *field == name) => absent.push(name),
+            result => return result,
+        }
+    }
+}
+
+/// The charset to decode a field with if its part doesn't name one. The `_charset_` field names
+/// the charset of the form but is itself sent as ASCII.
+pub(crate) fn fallback_charset<'c>(name: &str, form_charset: Option<&'c str>) -> Option<&'c str> {
+    if name == "_charset_" { None } else { form_charset }
+}
+
+#[derive(Clone)]
+enum Value<'a> {
+    Text(Cow<'a, str>),
+    File {
+        filename: Cow<'a, str>,
+        content_type: Option<&'a str>,
+        bytes: &'a [u8],
+    },
+}
+
+impl<'a> Value<'a> {
+    fn from_part(name: &str, part: &Part<'a>, form_charset: Option<&str>) -> Result<Self, FormError> {
+        let bytes = part.body().unwrap_or_default();
+        match part.decoded_filename() {
+            Some(filename) => Ok(Value::File {
+                filename,
+                content_type: part.content_type(),
+                bytes,
+            }),
+            None => part.text_with_fallback(form_charset)
+                .map(Value::Text)
+                .ok_or_else(|| FormError::new(format!(
+                    "field `{}` cannot be decoded as {}",
+                    name,
+                    part.charset().or(form_charset).unwrap_or("utf-8"),
+                ))),
+        }
+    }
+}
+
+struct FormDeserializer<'a> {
+    fields: Vec<(&'a str, Vec<Value<'a>>)>,
+    /// Struct fields that are absent from the form and have to be presented without values.
+    absent: &'a [String],
+}
+
+impl<'de, 'a> Deserializer<'de> for FormDeserializer<'a> {
+    type Error = FormError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_map(FieldsAccess {
+            fields: self.fields.into_iter(),
+            next: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FormError> {
+        let absent = self.absent.iter()
+            .filter(|name| fields.contains(&name.as_str()))
+            .map(|name| (name.as_str(), vec![]));
+        self.fields.extend(absent);
+        self.deserialize_any(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct FieldsAccess<'a> {
+    fields: std::vec::IntoIter<(&'a str, Vec<Value<'a>>)>,
+    next: Option<(&'a str, Vec<Value<'a>>)>,
+}
+
+impl<'de, 'a> MapAccess<'de> for FieldsAccess<'a> {
+    type Error = FormError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, FormError> {
+        match self.fields.next() {
+            None => Ok(None),
+            Some((name, values)) => {
+                self.next = Some((name, values));
+                seed.deserialize(name.into_deserializer()).map(Some)
+            },
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, FormError> {
+        let (name, values) = self.next.take()
+            .ok_or_else(|| FormError::new("value requested before key".to_string()))?;
+        seed.deserialize(ValuesDeserializer { name, values })
+    }
+}
+
+/// Deserializer for all values sent under one name.
+struct ValuesDeserializer<'a> {
+    name: &'a str,
+    values: Vec<Value<'a>>,
+}
+
+impl<'a> ValuesDeserializer<'a> {
+    fn single(mut self) -> Result<ValueDeserializer<'a>, FormError> {
+        if self.values.is_empty() {
+            Err(FormError::missing(self.name))
+        } else if self.values.len() == 1 {
+            Ok(ValueDeserializer { name: self.name, value: self.values.remove(0) })
+        } else {
+            Err(FormError::new(format!(
+                "expected a single value for field `{}`, found {}",
+                self.name,
+                self.values.len(),
+            )))
+        }
+    }
+
+    fn into_seq(self) -> ValuesAccess<'a> {
+        ValuesAccess {
+            name: self.name,
+            values: self.values.into_iter(),
+        }
+    }
+}
+
+macro_rules! deserialize_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for ValuesDeserializer<'a> {
+    type Error = FormError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        if self.values.len() == 1 {
+            self.single()?.deserialize_any(visitor)
+        } else {
+            visitor.visit_seq(self.into_seq())
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        if self.values.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_seq(self.into_seq())
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_seq(self.into_seq())
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, FormError> {
+        visitor.visit_seq(self.into_seq())
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, FormError> {
+        self.single()?.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FormError> {
+        self.single()?.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FormError> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_single! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_unit
+        deserialize_map deserialize_identifier
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_unit()
+    }
+}
+
+struct ValuesAccess<'a> {
+    name: &'a str,
+    values: std::vec::IntoIter<Value<'a>>,
+}
+
+impl<'de, 'a> SeqAccess<'de> for ValuesAccess<'a> {
+    type Error = FormError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, FormError> {
+        match self.values.next() {
+            None => Ok(None),
+            Some(value) => seed.deserialize(ValueDeserializer { name: self.name, value }).map(Some),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Deserializer for a single part.
+struct ValueDeserializer<'a> {
+    name: &'a str,
+    value: Value<'a>,
+}
+
+impl<'a> ValueDeserializer<'a> {
+    fn text(&self, expected: &str) -> Result<&str, FormError> {
+        match &self.value {
+            Value::Text(text) => Ok(text),
+            Value::File { .. } => Err(FormError::new(format!(
+                "expected {} for field `{}`, found a file",
+                expected,
+                self.name,
+            ))),
+        }
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident: $ty:ty,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+                let text = self.text(stringify!($ty))?;
+                let value = text.trim().parse::<$ty>().map_err(|_| FormError::new(format!(
+                    "invalid value {:?} for field `{}`, expected {}",
+                    text,
+                    self.name,
+                    stringify!($ty),
+                )))?;
+                visitor.$visit(value)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = FormError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        match self.value {
+            Value::Text(text) => visitor.visit_str(&text),
+            Value::File { filename, content_type, bytes } => visitor.visit_map(FileAccess {
+                filename,
+                content_type,
+                bytes,
+                field: 0,
+            }),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        match self.value {
+            Value::Text(text) => visitor.visit_str(&text),
+            Value::File { bytes, .. } => match std::str::from_utf8(bytes) {
+                Ok(text) => visitor.visit_str(text),
+                Err(_) => Err(FormError::new(format!("file in field `{}` is not valid UTF-8", self.name))),
+            },
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        match self.value {
+            Value::Text(text) => visitor.visit_bytes(text.as_bytes()),
+            Value::File { bytes, .. } => visitor.visit_bytes(bytes),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_seq(ValuesAccess {
+            name: self.name,
+            values: vec![self.value].into_iter(),
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, FormError> {
+        let text = self.text("an enum variant")?;
+        visitor.visit_enum(text.trim().into_deserializer())
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_char => visit_char: char,
+    }
+
+    forward_to_deserialize_any! {
+        unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Presents a file part as a map with `filename`, `content_type` and `bytes` entries.
+struct FileAccess<'a> {
+    filename: Cow<'a, str>,
+    content_type: Option<&'a str>,
+    bytes: &'a [u8],
+    field: usize,
+}
+
+impl<'de, 'a> MapAccess<'de> for FileAccess<'a> {
+    type Error = FormError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, FormError> {
+        if self.field == 1 && self.content_type.is_none() {
+            self.field += 1;
+        }
+        let key = match self.field {
+            0 => "filename",
+            1 => "content_type",
+            2 => "bytes",
+            _ => return Ok(None),
+        };
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, FormError> {
+        let value = match self.field {
+            0 => FileField::Str(&self.filename),
+            1 => FileField::Str(self.content_type.unwrap_or_default()),
+            _ => FileField::Bytes(self.bytes),
+        };
+        self.field += 1;
+        seed.deserialize(value)
+    }
+}
+
+enum FileField<'a> {
+    Str(&'a str),
+    Bytes(&'a [u8]),
+}
+
+impl<'de, 'a> Deserializer<'de> for FileField<'a> {
+    type Error = FormError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        match self {
+            FileField::Str(text) => visitor.visit_str(text),
+            FileField::Bytes(bytes) => visitor.visit_bytes(bytes),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, FormError> {
+        visitor.visit_some(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> Deserialize<'de> for UploadedFile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct(UPLOADED_FILE, &["filename", "content_type", "bytes"], UploadedFileVisitor)
+    }
+}
+
+struct UploadedFileVisitor;
+
+impl<'de> Visitor<'de> for UploadedFileVisitor {
+    type Value = UploadedFile;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a file part")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<UploadedFile, A::Error> {
+        let mut filename = None;
+        let mut content_type = None;
+        let mut bytes = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "filename" => filename = Some(map.next_value::<String>()?),
+                "content_type" => content_type = map.next_value::<Option<String>>()?,
+                "bytes" => bytes = Some(map.next_value::<ByteBuf>()?.0),
+                _ => { map.next_value::<de::IgnoredAny>()?; },
+            }
+        }
+        Ok(UploadedFile {
+            filename: filename.ok_or_else(|| de::Error::missing_field("filename"))?,
+            content_type,
+            bytes: bytes.ok_or_else(|| de::Error::missing_field("bytes"))?,
+        })
+    }
+}
+
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = ByteBuf;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(bytes.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(bytes))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(ByteBuf(bytes))
+    }
+}
+
+impl Serialize for UploadedFile {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut file = serializer.serialize_struct(UPLOADED_FILE, 3)?;
+        file.serialize_field("filename", &self.filename)?;
+        file.serialize_field("content_type", &self.content_type)?;
+        file.serialize_field("bytes", &Bytes(&self.bytes))?;
+        file.end()
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for Bytes<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+impl ser::Error for FormError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        FormError::new(msg.to_string())
+    }
+}
+
+/// An expected value of a form field, as produced by [`to_fields`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum FormValue {
+    /// A string, compared exactly with the decoded text of the part.
+    Text(String),
+    Scalar(Scalar),
+    Bytes(Vec<u8>),
+    File(UploadedFile),
+}
+
+/// A value that [`from_parts`] parses from the trimmed text of a part, so it is compared by
+/// value: `1.0` matches an expected `1.0_f64` although that serializes as `1`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum Scalar {
+    Bool(bool),
+    Signed(i128),
+    Unsigned(u128),
+    /// The bits of an `f32`, parsed as `f32` so that e.g. `0.1` still matches.
+    F32(u32),
+    /// The bits of an `f64`.
+    F64(u64),
+    Char(char),
+    Variant(String),
+}
+
+impl Scalar {
+    fn matches_text(&self, text: &str) -> bool {
+        let text = text.trim();
+        match self {
+            Scalar::Bool(value) => text.parse() == Ok(*value),
+            Scalar::Signed(value) => text.parse() == Ok(*value),
+            Scalar::Unsigned(value) => text.parse() == Ok(*value),
+            Scalar::F32(bits) => text.parse() == Ok(f32::from_bits(*bits)),
+            Scalar::F64(bits) => text.parse() == Ok(f64::from_bits(*bits)),
+            Scalar::Char(value) => text.parse() == Ok(*value),
+            Scalar::Variant(variant) => text == variant,
+        }
+    }
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scalar::Bool(value) => value.fmt(f),
+            Scalar::Signed(value) => value.fmt(f),
+            Scalar::Unsigned(value) => value.fmt(f),
+            Scalar::F32(bits) => f32::from_bits(*bits).fmt(f),
+            Scalar::F64(bits) => f64::from_bits(*bits).fmt(f),
+            Scalar::Char(value) => value.fmt(f),
+            Scalar::Variant(variant) => variant.fmt(f),
+        }
+    }
+}
+
+impl FormValue {
+    /// Whether the part holds this value, decoding text like [`from_parts`] does.
+    pub(crate) fn matches_part(&self, part: &Part, fallback_charset: Option<&str>) -> bool {
+        let body = part.body().unwrap_or_default();
+        let text = || part.text_with_fallback(fallback_charset).filter(|_| !part.has_file_input());
+        match self {
+            FormValue::Text(expected) => text().map(|text| text == expected.as_str()).unwrap_or(false),
+            FormValue::Scalar(scalar) => text().map(|text| scalar.matches_text(&text)).unwrap_or(false),
+            FormValue::Bytes(bytes) => !part.has_file_input() && body == bytes.as_slice(),
+            FormValue::File(file) => {
+                part.decoded_filename().as_deref() == Some(file.filename.as_str())
+                    && part.content_type() == file.content_type.as_deref()
+                    && body == file.bytes.as_slice()
+            },
+        }
+    }
+}
+
+/// Serialize `value` into the form fields it would be deserialized from.
+///
+/// This mirrors [`from_parts`]: scalars become text fields, sequences become repeated fields,
+/// `None` fields and empty sequences are left out and [`UploadedFile`]s become file parts.
+pub(crate) fn to_fields<T: Serialize + ?Sized>(value: &T) -> Result<Fields, FormError> {
+    value.serialize(FormSerializer)
+}
+
+fn unsupported<T>(what: &str) -> Result<T, FormError> {
+    Err(FormError::new(format!("{} cannot be represented as multipart form fields", what)))
+}
+
+type Fields = Vec<(String, Vec<FormValue>)>;
+
+struct FormSerializer;
+
+impl Serializer for FormSerializer {
+    type Ok = Fields;
+    type Error = FormError;
+    type SerializeSeq = Impossible<Fields, FormError>;
+    type SerializeTuple = Impossible<Fields, FormError>;
+    type SerializeTupleStruct = Impossible<Fields, FormError>;
+    type SerializeTupleVariant = Impossible<Fields, FormError>;
+    type SerializeMap = FieldsCollector;
+    type SerializeStruct = FieldsCollector;
+    type SerializeStructVariant = Impossible<Fields, FormError>;
+
+    fn serialize_bool(self, _v: bool) -> Result<Fields, FormError> { unsupported("a bool") }
+    fn serialize_i8(self, _v: i8) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_i16(self, _v: i16) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_i32(self, _v: i32) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_i64(self, _v: i64) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_u8(self, _v: u8) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_u16(self, _v: u16) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_u32(self, _v: u32) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_u64(self, _v: u64) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_f32(self, _v: f32) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_f64(self, _v: f64) -> Result<Fields, FormError> { unsupported("a number") }
+    fn serialize_char(self, _v: char) -> Result<Fields, FormError> { unsupported("a char") }
+    fn serialize_str(self, _v: &str) -> Result<Fields, FormError> { unsupported("a string") }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Fields, FormError> { unsupported("bytes") }
+    fn serialize_none(self) -> Result<Fields, FormError> { unsupported("none") }
+    fn serialize_unit(self) -> Result<Fields, FormError> { unsupported("a unit") }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Fields, FormError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Fields, FormError> {
+        Ok(vec![])
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, _variant: &'static str) -> Result<Fields, FormError> {
+        unsupported("an enum")
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Fields, FormError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Fields, FormError> {
+        unsupported("an enum")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, FormError> { unsupported("a sequence") }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, FormError> { unsupported("a tuple") }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, FormError> {
+        unsupported("a tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, FormError> {
+        unsupported("an enum")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<FieldsCollector, FormError> {
+        Ok(FieldsCollector::default())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<FieldsCollector, FormError> {
+        Ok(FieldsCollector::default())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, FormError> {
+        unsupported("an enum")
+    }
+}
+
+#[derive(Default)]
+struct FieldsCollector {
+    fields: Fields,
+    key: Option<String>,
+}
+
+impl FieldsCollector {
+    fn push(&mut self, name: String, values: Vec<FormValue>) {
+        if !values.is_empty() {
+            self.fields.push((name, values));
+        }
+    }
+}
+
+impl SerializeMap for FieldsCollector {
+    type Ok = Fields;
+    type Error = FormError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), FormError> {
+        match key.serialize(FieldSerializer)?.pop() {
+            Some(FormValue::Text(key)) => {
+                self.key = Some(key);
+                Ok(())
+            },
+            Some(FormValue::Scalar(key)) => {
+                self.key = Some(key.to_string());
+                Ok(())
+            },
+            _ => unsupported("a non-text map key"),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FormError> {
+        let key = self.key.take()
+            .ok_or_else(|| FormError::new("value serialized before key".to_string()))?;
+        let values = value.serialize(FieldSerializer)?;
+        self.push(key, values);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Fields, FormError> {
+        Ok(self.fields)
+    }
+}
+
+impl SerializeStruct for FieldsCollector {
+    type Ok = Fields;
+    type Error = FormError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), FormError> {
+        let values = value.serialize(FieldSerializer)?;
+        self.push(key.to_string(), values);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Fields, FormError> {
+        Ok(self.fields)
+    }
+}
+
+/// Serializes the value of a single field into zero or more form values.
+struct FieldSerializer;
+
+fn text<T: ToString>(value: T) -> Result<Vec<FormValue>, FormError> {
+    Ok(vec![FormValue::Text(value.to_string())])
+}
+
+fn scalar(value: Scalar) -> Result<Vec<FormValue>, FormError> {
+    Ok(vec![FormValue::Scalar(value)])
+}
+
+impl Serializer for FieldSerializer {
+    type Ok = Vec<FormValue>;
+    type Error = FormError;
+    type SerializeSeq = ValuesCollector;
+    type SerializeTuple = ValuesCollector;
+    type SerializeTupleStruct = ValuesCollector;
+    type SerializeTupleVariant = Impossible<Vec<FormValue>, FormError>;
+    type SerializeMap = Impossible<Vec<FormValue>, FormError>;
+    type SerializeStruct = FileCollector;
+    type SerializeStructVariant = Impossible<Vec<FormValue>, FormError>;
+
+    fn serialize_bool(self, v: bool) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Bool(v)) }
+    fn serialize_i8(self, v: i8) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Signed(v.into())) }
+    fn serialize_i16(self, v: i16) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Signed(v.into())) }
+    fn serialize_i32(self, v: i32) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Signed(v.into())) }
+    fn serialize_i64(self, v: i64) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Signed(v.into())) }
+    fn serialize_i128(self, v: i128) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Signed(v)) }
+    fn serialize_u8(self, v: u8) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Unsigned(v.into())) }
+    fn serialize_u16(self, v: u16) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Unsigned(v.into())) }
+    fn serialize_u32(self, v: u32) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Unsigned(v.into())) }
+    fn serialize_u64(self, v: u64) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Unsigned(v.into())) }
+    fn serialize_u128(self, v: u128) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Unsigned(v)) }
+    fn serialize_f32(self, v: f32) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::F32(v.to_bits())) }
+    fn serialize_f64(self, v: f64) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::F64(v.to_bits())) }
+    fn serialize_char(self, v: char) -> Result<Vec<FormValue>, FormError> { scalar(Scalar::Char(v)) }
+    fn serialize_str(self, v: &str) -> Result<Vec<FormValue>, FormError> { text(v) }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Vec<FormValue>, FormError> {
+        Ok(vec![FormValue::Bytes(v.to_vec())])
+    }
+
+    fn serialize_none(self) -> Result<Vec<FormValue>, FormError> {
+        Ok(vec![])
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<FormValue>, FormError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Vec<FormValue>, FormError> {
+        text("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Vec<FormValue>, FormError> {
+        text("")
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Vec<FormValue>, FormError> {
+        scalar(Scalar::Variant(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Vec<FormValue>, FormError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Vec<FormValue>, FormError> {
+        unsupported("an enum variant with data")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<ValuesCollector, FormError> {
+        Ok(ValuesCollector::default())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<ValuesCollector, FormError> {
+        Ok(ValuesCollector::default())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<ValuesCollector, FormError> {
+        Ok(ValuesCollector::default())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, FormError> {
+        unsupported("an enum variant with data")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, FormError> {
+        unsupported("a nested map")
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<FileCollector, FormError> {
+        if name == UPLOADED_FILE {
+            Ok(FileCollector::default())
+        } else {
+            unsupported("a nested struct")
+        }
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, FormError> {
+        unsupported("an enum variant with data")
+    }
+}
+
+#[derive(Default)]
+struct ValuesCollector {
+    values: Vec<FormValue>,
+}
+
+impl ValuesCollector {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FormError> {
+        self.values.extend(value.serialize(FieldSerializer)?);
+        Ok(())
+    }
+}
+
+impl SerializeSeq for ValuesCollector {
+    type Ok = Vec<FormValue>;
+    type Error = FormError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FormError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<FormValue>, FormError> {
+        Ok(self.values)
+    }
+}
+
+impl SerializeTuple for ValuesCollector {
+    type Ok = Vec<FormValue>;
+    type Error = FormError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FormError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<FormValue>, FormError> {
+        Ok(self.values)
+    }
+}
+
+impl SerializeTupleStruct for ValuesCollector {
+    type Ok = Vec<FormValue>;
+    type Error = FormError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FormError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Vec<FormValue>, FormError> {
+        Ok(self.values)
+    }
+}
+
+/// Collects the fields of a serialized [`UploadedFile`].
+#[derive(Default)]
+struct FileCollector {
+    filename: Option<String>,
+    content_type: Option<String>,
+    bytes: Vec<u8>,
+}
+
+impl SerializeStruct for FileCollector {
+    type Ok = Vec<FormValue>;
+    type Error = FormError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), FormError> {
+        let value = value.serialize(FieldSerializer)?.pop();
+        match (key, value) {
+            ("filename", Some(FormValue::Text(filename))) => self.filename = Some(filename),
+            ("content_type", Some(FormValue::Text(content_type))) => self.content_type = Some(content_type),
+            ("content_type", None) => self.content_type = None,
+            ("bytes", Some(FormValue::Bytes(bytes))) => self.bytes = bytes,
+            ("bytes", Some(FormValue::Text(text))) => self.bytes = text.into_bytes(),
+            _ => return unsupported(&format!("file field `{}`", key)),
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Vec<FormValue>, FormError> {
+        Ok(vec![FormValue::File(UploadedFile {
+            filename: self.filename.ok_or_else(|| FormError::new("file without filename".to_string()))?,
+            content_type: self.content_type,
+            bytes: self.bytes,
+        })])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+    use serde::Deserialize;
+
+    use crate::request_utils::RequestUtils;
+    use crate::test_utils::{form_request, name, requestb, values};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Visibility {
+        Public,
+        Private,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Upload {
+        title: String,
+        count: u32,
+        ratio: f64,
+        draft: bool,
+        visibility: Visibility,
+        tags: Vec<String>,
+        description: Option<String>,
+        attachment: UploadedFile,
+    }
+
+    #[test]
+    fn should_deserialize_form_into_struct() {
+        let request = form_request()
+            .field("title", "Holiday")
+            .field("count", "42")
+            .field("ratio", "0.75")
+            .field("draft", "true")
+            .field("visibility", "private")
+            .field("tags", "beach")
+            .field("tags", "sunset")
+            .file("attachment", "notes.txt", "text/plain", "some notes")
+            .build();
+
+        assert_eq!(
+            request.form::<Upload>(),
+            Ok(Upload {
+                title: "Holiday".into(),
+                count: 42,
+                ratio: 0.75,
+                draft: true,
+                visibility: Visibility::Private,
+                tags: vec!["beach".into(), "sunset".into()],
+                description: None,
+                attachment: UploadedFile {
+                    filename: "notes.txt".into(),
+                    content_type: Some("text/plain".into()),
+                    bytes: b"some notes".to_vec(),
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn should_collect_single_value_into_vec() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Form {
+            title: Vec<String>,
+            description: Option<String>,
+        }
+
+        let request = form_request().field("title", "Holiday").build();
+
+        assert_eq!(
+            request.form::<Form>(),
+            Ok(Form { title: vec!["Holiday".into()], description: None }),
+        );
+    }
+
+    #[test]
+    fn should_default_absent_sequences_to_empty() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Form {
+            title: String,
+            tags: Vec<String>,
+            #[serde(default)]
+            count: u32,
+            description: Option<String>,
+        }
+
+        let request = form_request().field("title", "Holiday").build();
+
+        assert_eq!(
+            request.form::<Form>(),
+            Ok(Form { title: "Holiday".into(), tags: vec![], count: 0, description: None }),
+        );
+    }
+
+    #[test]
+    fn should_report_missing_fields() {
+        #[derive(Debug, Deserialize)]
+        struct Form {
+            #[allow(dead_code)]
+            title: String,
+            #[allow(dead_code)]
+            tags: Vec<String>,
+        }
+
+        let request = form_request().field("description", "sunny").build();
+
+        assert_eq!(request.form::<Form>().unwrap_err().to_string(), "missing field `title`");
+    }
+
+    #[test]
+    fn should_round_trip_empty_sequences() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Form {
+            title: String,
+            tags: Vec<String>,
+        }
+
+        let form = Form { title: "Holiday".into(), tags: vec![] };
+        let fields = to_fields(&form).unwrap();
+        let request = fields.iter()
+            .fold(form_request(), |request, (name, values)| match &values[..] {
+                [FormValue::Text(value)] => request.field(name, value),
+                _ => unreachable!(),
+            })
+            .build();
+
+        assert_eq!(fields.len(), 1);
+        assert_eq!(request.form::<Form>(), Ok(form));
+    }
+
+    #[test]
+    fn should_report_invalid_values() {
+        #[derive(Debug, Deserialize)]
+        struct Form {
+            #[allow(dead_code)]
+            title: u32,
+        }
+
+        let request = form_request().field("title", "Holiday").build();
+
+        assert_eq!(
+            request.form::<Form>().unwrap_err().to_string(),
+            "invalid value \"Holiday\" for field `title`, expected u32",
+        );
+    }
+
+    #[test]
+    fn should_report_repeated_values_for_single_field() {
+        #[derive(Debug, Deserialize)]
+        struct Form {
+            #[allow(dead_code)]
+            tags: String,
+        }
+
+        let request = form_request()
+            .field("tags", "beach")
+            .field("tags", "sunset")
+            .build();
+
+        assert_eq!(
+            request.form::<Form>().unwrap_err().to_string(),
+            "expected a single value for field `tags`, found 2",
+        );
+    }
+
+    #[test]
+    fn should_report_text_that_cannot_be_decoded() {
+        #[derive(Debug, Deserialize)]
+        struct Form {
+            #[allow(dead_code)]
+            title: String,
+        }
+
+        let request = form_request()
+            .field("_charset_", "x-unknown")
+            .field("title", "caf\u{e9}")
+            .build();
+
+        assert_eq!(
+            request.form::<Form>().unwrap_err().to_string(),
+            "field `title` cannot be decoded as x-unknown",
+        );
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn should_decode_fields_with_form_and_part_charset() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Form {
+            title: String,
+            city: String,
+        }
+
+        let request = form_request()
+            .field("_charset_", "windows-1252")
+            .part(&["Content-Disposition: form-data; name=\"title\""], b"caf\xe9")
+            .part(&["Content-Disposition: form-data; name=\"city\"", "Content-Type: text/plain; charset=utf-8"], "K\u{f6}ln")
+            .build();
+
+        assert_eq!(
+            request.form::<Form>(),
+            Ok(Form { title: "caf\u{e9}".into(), city: "K\u{f6}ln".into() }),
+        );
+    }
+
+    #[test]
+    fn should_reject_non_multipart_requests() {
+        let request = requestb(
+            hashmap!{
+                name("content-type") => values("application/json"),
+            },
+            "{}".as_bytes().into(),
+        );
+
+        assert!(request.form::<Option<String>>().is_err());
+    }
+}
@@ -6,7 +6,7 @@
 //! ```toml
 //! [dev-dependencies]
 //! # ...
-//! wiremock-multipart = "0.1"
+//! wiremock-multipart = "2.0"
 //! ```
 //!
 //! ## Getting started
@@ -33,6 +33,19 @@
 //!     // will match and return 200.
 //! }
 //! ```
+//!
+//! ## Features
+//! - `archive`: inspect the entries of ZIP, tar and tar.gz parts with
+//!   [`ArchivePart`](matchers::ArchivePart).
+//! - `serde`: deserialize form fields into typed structs with
+//!   [`RequestUtils::form`](RequestUtils::form) and match forms against serializable values
+//!   with [`FormEquals`](matchers::FormEquals). Also makes [`PartSpec`](matchers::PartSpec)
+//!   serializable.
+//! - `encoding`: decode part bodies in charsets other than UTF-8, see [`Part::text`].
+//! - `fixtures`: load expected parts from YAML or JSON files with
+//!   [`MultipartFixture`](matchers::MultipartFixture).
+//! - `mappings`: reuse the `multipartPatterns` of Java WireMock stub mappings with
+//!   [`MultipartPatterns`](matchers::MultipartPatterns).
 
 #[cfg(test)]
 extern crate indoc;
@@ -41,12 +54,31 @@ extern crate lazy_regex;
 extern crate maplit;
 extern crate wiremock;
 
+#[cfg(feature = "archive")]
+mod archive;
+mod assignment;
+mod builder;
+mod byteranges;
+mod content_range;
+#[cfg(feature = "serde")]
+mod form;
 pub mod matchers;
 mod part;
 mod request_utils;
+mod response;
+mod text;
 
+#[cfg(feature = "archive")]
+pub use archive::{ArchiveEntry, ArchiveFormat};
+pub use builder::{MultipartBody, MultipartBuilder, PartBody, PartBuilder, TransferEncoding};
+pub use byteranges::ByteRangesResponder;
+pub use content_range::ContentRange;
+#[cfg(feature = "serde")]
+pub use form::{FormError, UploadedFile};
 pub use part::Part;
 pub use request_utils::{MultipartContentType, RequestUtils};
+pub use response::MultipartResponseTemplate;
+pub use text::{NameNormalization, TextNormalization, UnicodeForm};
 
 pub mod prelude {
     pub use crate::matchers::*;
@@ -56,10 +88,12 @@ pub mod prelude {
 mod test_utils {
     use maplit::hashmap;
     use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
     use std::str::FromStr;
 
     use wiremock::http::{HeaderName, HeaderValue, Method, Url};
-    use wiremock::Request;
+    use wiremock::{MockServer, Request};
 
     pub fn name(name: &'static str) -> HeaderName {
         HeaderName::from_str(name).unwrap()
@@ -90,4 +124,79 @@ mod test_utils {
             name("content-type") => values("multipart/form-data; boundary=xyz"),
         }
     }
+
+    pub fn form_request() -> FormRequest {
+        FormRequest::default()
+    }
+
+    /// Builds a multipart/form-data request with the boundary of [`multipart_header`], using
+    /// CRLF line breaks.
+    #[derive(Default)]
+    pub struct FormRequest {
+        body: Vec<u8>,
+    }
+
+    impl FormRequest {
+        pub fn field(self, name: &str, value: &str) -> Self {
+            self.part(&[&format!("Content-Disposition: form-data; name=\"{}\"", name)], value)
+        }
+
+        pub fn file(self, name: &str, filename: &str, content_type: &str, content: impl AsRef<[u8]>) -> Self {
+            self.part(
+                &[
+                    &format!("Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"", name, filename),
+                    &format!("Content-Type: {}", content_type),
+                ],
+                content,
+            )
+        }
+
+        pub fn part(mut self, headers: &[&str], body: impl AsRef<[u8]>) -> Self {
+            self.body.extend_from_slice(b"--xyz\r\n");
+            for header in headers {
+                self.body.extend_from_slice(header.as_bytes());
+                self.body.extend_from_slice(b"\r\n");
+            }
+            self.body.extend_from_slice(b"\r\n");
+            self.body.extend_from_slice(body.as_ref());
+            self.body.extend_from_slice(b"\r\n");
+            self
+        }
+
+        pub fn build(mut self) -> Request {
+            self.body.extend_from_slice(b"--xyz--\r\n");
+            requestb(multipart_header(), self.body)
+        }
+    }
+
+    /// Send a bare HTTP/1.1 request and return status, headers and body of the response.
+    pub fn get(server: &MockServer, headers: &[(&str, &str)]) -> (u16, Vec<(String, String)>, Vec<u8>) {
+        let mut stream = TcpStream::connect(server.address()).unwrap();
+        let mut request = "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n".to_string();
+        for (name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = vec![];
+        stream.read_to_end(&mut response).unwrap();
+        let head_end = response.windows(4).position(|window| window == b"\r\n\r\n").unwrap();
+        let head = String::from_utf8(response[..head_end].to_vec()).unwrap();
+        let body = response[head_end + 4..].to_vec();
+
+        let mut lines = head.split("\r\n");
+        let status = lines.next().unwrap().split(' ').nth(1).unwrap().parse().unwrap();
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+        (status, headers, body)
+    }
+
+    pub fn header<'h>(headers: &'h [(String, String)], name: &str) -> Option<&'h str> {
+        headers.iter()
+            .find(|(header_name, _)| header_name == name)
+            .map(|(_, value)| value.as_str())
+    }
 }
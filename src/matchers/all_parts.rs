@@ -0,0 +1,176 @@
+use wiremock::{Match, Request};
+
+use crate::matchers::ContainsPart;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart request in which every part matches the given criteria.
+///
+/// Presence and size criteria such as [`ContainsPart::with_any_name`],
+/// [`ContainsPart::with_any_header`] and [`ContainsPart::with_max_body_size`] express rules that
+/// must hold for every part. Use [`AllParts::files`] or [`AllParts::matching`] to apply them
+/// only to some parts, e.g. that each upload stays below a size limit while text fields are
+/// not checked.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(AllParts::new(ContainsPart::new().with_any_name()))
+///         .and(AllParts::files(ContainsPart::new().with_max_body_size(10 * 1024 * 1024)))
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct AllParts<'a, 'b, 'c, 'd> {
+    /// The parts that have to match `part`, all parts if `None`.
+    pub selector: Option<ContainsPart<'a, 'b, 'c, 'd>>,
+    pub part: ContainsPart<'a, 'b, 'c, 'd>,
+}
+
+impl<'a, 'b, 'c, 'd> AllParts<'a, 'b, 'c, 'd> {
+    pub fn new(part: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        AllParts { selector: None, part }
+    }
+
+    /// Require only the parts that match `selector` to match `part`. Requests without such
+    /// parts match.
+    pub fn matching(selector: ContainsPart<'a, 'b, 'c, 'd>, part: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        AllParts { selector: Some(selector), part }
+    }
+
+    /// Require only the parts sent by file inputs, i.e. with a `filename`, to match `part`.
+    pub fn files(part: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        Self::matching(ContainsPart::new().with_any_filename(), part)
+    }
+}
+
+impl<'a, 'b, 'c, 'd> Match for AllParts<'a, 'b, 'c, 'd> {
+    fn matches(&self, request: &Request) -> bool {
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        request.is_multipart()
+            && parts.iter()
+                .filter(|part| self.selector.as_ref().map(|selector| selector.matches_part_with_charset(part, form_charset)).unwrap_or(true))
+                .all(|part| self.part.matches_part_with_charset(part, form_charset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+
+    use crate::test_utils::{form_request, name, requestb, values};
+
+    use super::*;
+
+    #[test]
+    fn should_match_if_every_part_matches() {
+        let request = form_request()
+            .file("first", "a.png", "image/png", "png")
+            .file("second", "b.png", "image/png", "png")
+            .build();
+
+        assert!(AllParts::new(ContainsPart::new().with_content_type("image/png")).matches(&request));
+        assert!(AllParts::new(ContainsPart::new()).matches(&request));
+    }
+
+    #[test]
+    fn should_not_match_if_a_part_does_not_match() {
+        let request = form_request()
+            .file("first", "a.png", "image/png", "png")
+            .file("second", "b.png", "image/png", "png")
+            .build();
+
+        assert!(!AllParts::new(ContainsPart::new().with_name("first")).matches(&request));
+    }
+
+    #[test]
+    fn should_match_if_every_part_has_a_name() {
+        let named = form_request()
+            .field("first", "a")
+            .field("", "b")
+            .build();
+        let unnamed = form_request()
+            .field("first", "a")
+            .part(&["Content-Disposition: attachment"], "b")
+            .build();
+
+        assert!(AllParts::new(ContainsPart::new().with_any_name()).matches(&named));
+        assert!(!AllParts::new(ContainsPart::new().with_any_name()).matches(&unnamed));
+    }
+
+    #[test]
+    fn should_match_if_every_part_has_a_content_type() {
+        let typed = form_request()
+            .file("first", "a.png", "image/png", "png")
+            .file("second", "b.png", "image/png", "png")
+            .build();
+        let untyped = form_request()
+            .file("first", "a.png", "image/png", "png")
+            .field("comment", "no content type")
+            .build();
+
+        assert!(AllParts::new(ContainsPart::new().with_any_header("Content-Type")).matches(&typed));
+        assert!(!AllParts::new(ContainsPart::new().with_any_header("Content-Type")).matches(&untyped));
+    }
+
+    #[test]
+    fn should_match_if_every_file_is_under_10_mb() {
+        let limit = 10 * 1024 * 1024;
+        let upload = |size: usize| {
+            form_request()
+                .field("comment", "a text field without filename")
+                .file("small", "a.bin", "application/octet-stream", "small")
+                .file("large", "b.bin", "application/octet-stream", vec![b'x'; size])
+                .build()
+        };
+        let every_file_under_10_mb = AllParts::files(ContainsPart::new().with_max_body_size(limit));
+
+        assert!(every_file_under_10_mb.matches(&upload(limit)));
+        assert!(!every_file_under_10_mb.matches(&upload(limit + 1)));
+        assert!(every_file_under_10_mb.matches(&form_request().field("comment", "no files").build()));
+    }
+
+    #[test]
+    fn should_only_check_selected_parts() {
+        let request = form_request()
+            .field("comment", "text")
+            .file("first", "a.png", "image/png", "png")
+            .file("second", "b.png", "image/png", "png")
+            .build();
+
+        assert!(!AllParts::new(ContainsPart::new().with_content_type("image/png")).matches(&request));
+        assert!(AllParts::files(ContainsPart::new().with_content_type("image/png")).matches(&request));
+        assert!(!AllParts::matching(
+            ContainsPart::new().with_content_type("image/png"),
+            ContainsPart::new().with_filename_basename("a.png"),
+        ).matches(&request));
+        assert!(AllParts::matching(
+            ContainsPart::new().with_name("comment"),
+            ContainsPart::new().with_text("text"),
+        ).matches(&request));
+    }
+
+    #[test]
+    fn should_not_match_non_multipart_request() {
+        let request = requestb(
+            hashmap!{
+                name("content-type") => values("text/plain"),
+            },
+            vec![],
+        );
+
+        assert!(!AllParts::new(ContainsPart::new()).matches(&request));
+    }
+}
@@ -0,0 +1,167 @@
+use wiremock::{Match, Request};
+
+use crate::matchers::ContainsPart;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Matcher builder to assert on the entries of a ZIP, tar or tar.gz part.
+///
+/// Archives are compared by their entries rather than their bytes, so timestamps and
+/// compression settings don't matter. Only available with the `archive` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(archive_contains_entry("data/report.csv")
+///             .in_part(ContainsPart::new().with_name("export"))
+///             .with_entry_count(2))
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct ArchivePart<'a, 'b, 'c, 'd> {
+    pub part: ContainsPart<'a, 'b, 'c, 'd>,
+    pub entries: Vec<String>,
+    pub entry_count: Option<usize>,
+    pub entry_contents: Vec<(String, Vec<u8>)>,
+}
+
+/// Shorthand for `ArchivePart::new().with_entry(path)`.
+pub fn archive_contains_entry<T: Into<String>>(path: T) -> ArchivePart<'static, 'static, 'static, 'static> {
+    ArchivePart::new().with_entry(path)
+}
+
+impl<'a, 'b, 'c, 'd> ArchivePart<'a, 'b, 'c, 'd> {
+    pub fn new() -> Self { Self::default() }
+
+    /// Restrict the archive checks to parts matching the given criteria.
+    pub fn in_part<'e, 'f, 'g, 'h>(self, part: ContainsPart<'e, 'f, 'g, 'h>) -> ArchivePart<'e, 'f, 'g, 'h> {
+        ArchivePart {
+            part,
+            entries: self.entries,
+            entry_count: self.entry_count,
+            entry_contents: self.entry_contents,
+        }
+    }
+
+    pub fn with_entry<T: Into<String>>(mut self, path: T) -> Self {
+        self.entries.push(path.into());
+        self
+    }
+
+    pub fn with_entry_count(self, entry_count: usize) -> Self {
+        ArchivePart {
+            entry_count: Some(entry_count),
+            ..self
+        }
+    }
+
+    pub fn with_entry_content<T: Into<String>, C: Into<Vec<u8>>>(mut self, path: T, content: C) -> Self {
+        self.entry_contents.push((path.into(), content.into()));
+        self
+    }
+}
+
+impl<'a, 'b, 'c, 'd> Match for ArchivePart<'a, 'b, 'c, 'd> {
+    fn matches(&self, request: &Request) -> bool {
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        parts.iter()
+            .filter(|part| self.part.matches_part_with_charset(part, form_charset))
+            .filter_map(|part| part.archive_entries())
+            .any(|archive| {
+                let entries = self.entries.iter()
+                    .all(|path| archive.iter().any(|entry| &entry.path == path));
+
+                let entry_count = self.entry_count
+                    .map(|count| archive.len() == count)
+                    .unwrap_or(true);
+
+                let entry_contents = self.entry_contents.iter()
+                    .all(|(path, content)| {
+                        archive.iter().any(|entry| &entry.path == path && &entry.content == content)
+                    });
+
+                entries && entry_count && entry_contents
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::Request;
+
+    use crate::archive::test_archives::*;
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    fn request_with_archive(archive: &[u8]) -> Request {
+        form_request()
+            .field("meta", "{}")
+            .file("export", "export.zip", "application/zip", archive)
+            .build()
+    }
+
+    const FILES: &[(&str, &[u8])] = &[
+        ("data/report.csv", b"a,b\n1,2\n"),
+        ("README", b"hello"),
+    ];
+
+    #[test]
+    fn should_match_on_entry_presence() {
+        for archive in [zip(FILES), tar(FILES), tar_gz(FILES)] {
+            let request = request_with_archive(&archive);
+
+            assert!(archive_contains_entry("data/report.csv").matches(&request));
+            assert!(archive_contains_entry("README").with_entry("data/report.csv").matches(&request));
+            assert!(!archive_contains_entry("data/other.csv").matches(&request));
+        }
+    }
+
+    #[test]
+    fn should_match_on_entry_count() {
+        let request = request_with_archive(&zip(FILES));
+
+        assert!(ArchivePart::new().with_entry_count(2).matches(&request));
+        assert!(!ArchivePart::new().with_entry_count(3).matches(&request));
+    }
+
+    #[test]
+    fn should_match_on_entry_content() {
+        let request = request_with_archive(&tar_gz(FILES));
+
+        assert!(ArchivePart::new().with_entry_content("README", "hello").matches(&request));
+        assert!(!ArchivePart::new().with_entry_content("README", "goodbye").matches(&request));
+    }
+
+    #[test]
+    fn should_only_inspect_selected_parts() {
+        let request = request_with_archive(&zip(FILES));
+
+        assert!(archive_contains_entry("README")
+            .in_part(ContainsPart::new().with_name("export"))
+            .matches(&request));
+        assert!(!archive_contains_entry("README")
+            .in_part(ContainsPart::new().with_name("meta"))
+            .matches(&request));
+    }
+
+    #[test]
+    fn should_not_match_request_without_archive() {
+        let request = form_request().field("a", "text").build();
+
+        assert!(!ArchivePart::new().matches(&request));
+    }
+}
@@ -1,8 +1,12 @@
 use std::borrow::Cow;
 
+use lazy_regex::Regex;
 use wiremock::{Match, Request};
 
+use crate::matchers::PartCount;
+use crate::part::Part;
 use crate::request_utils::RequestUtils;
+use crate::text::{NameNormalization, TextNormalization};
 
 /// Matcher builder to assert the presence of a matching part in the request.
 ///
@@ -28,12 +32,85 @@ use crate::request_utils::RequestUtils;
 ///         .await;
 /// }
 /// ```
-#[derive(Default, Debug, PartialEq, Eq)]
+///
+/// The lifetimes belong to the name, filename, header (content type, other headers and
+/// disposition) and body (bytes or text) criteria, in that order. New criteria may be added in
+/// minor releases, so build the matcher with [`ContainsPart::new`] and the `with_*` methods.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct ContainsPart<'a, 'b, 'c, 'd> {
     pub name: Option<Cow<'a, str>>,
+    pub any_name: bool,
     pub filename: Option<Cow<'b, str>>,
+    pub any_filename: bool,
+    pub filename_basename: Option<Cow<'b, str>>,
     pub content_type: Option<Cow<'c, str>>,
     pub body: Option<Cow<'d, [u8]>>,
+    pub max_body_size: Option<usize>,
+    pub headers: Vec<(Cow<'c, str>, HeaderCondition)>,
+    pub disposition: Option<Cow<'c, str>>,
+    pub disposition_params: Vec<(Cow<'c, str>, Cow<'c, str>)>,
+    pub text: Option<Cow<'d, str>>,
+    pub text_normalization: TextNormalization,
+    pub name_normalization: NameNormalization,
+}
+
+/// Condition on a part header, see [`ContainsPart::with_header`] and friends.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum HeaderCondition {
+    Equals(String),
+    EqualsBytes(Vec<u8>),
+    Matches(#[cfg_attr(feature = "serde", serde(with = "regex_serde"))] Regex),
+    Present,
+    Absent,
+}
+
+#[cfg(feature = "serde")]
+mod regex_serde {
+    use lazy_regex::Regex;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(regex: &Regex, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(regex.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Regex::new(&pattern).map_err(de::Error::custom)
+    }
+}
+
+impl PartialEq for HeaderCondition {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HeaderCondition::Equals(a), HeaderCondition::Equals(b)) => a == b,
+            (HeaderCondition::EqualsBytes(a), HeaderCondition::EqualsBytes(b)) => a == b,
+            (HeaderCondition::Matches(a), HeaderCondition::Matches(b)) => a.as_str() == b.as_str(),
+            (HeaderCondition::Present, HeaderCondition::Present) => true,
+            (HeaderCondition::Absent, HeaderCondition::Absent) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for HeaderCondition {}
+
+impl HeaderCondition {
+    fn matches_part(&self, name: &str, part: &Part) -> bool {
+        let mut values = part.raw_headers()
+            .into_iter()
+            .filter(|(header_name, _)| header_name.eq_ignore_ascii_case(name.as_bytes()))
+            .map(|(_, value)| value);
+
+        match self {
+            HeaderCondition::Equals(expected) => values.any(|value| value == expected.as_bytes()),
+            HeaderCondition::EqualsBytes(expected) => values.any(|value| value == expected.as_slice()),
+            HeaderCondition::Matches(regex) => values.any(|value| regex.is_match(&String::from_utf8_lossy(value))),
+            HeaderCondition::Present => values.next().is_some(),
+            HeaderCondition::Absent => values.next().is_none(),
+        }
+    }
 }
 
 impl<'a, 'b, 'c, 'd> ContainsPart<'a, 'b, 'c, 'd> {
@@ -46,6 +123,14 @@ impl<'a, 'b, 'c, 'd> ContainsPart<'a, 'b, 'c, 'd> {
         }
     }
 
+    /// Require the part to have a name, whatever it is.
+    pub fn with_any_name(self) -> Self {
+        ContainsPart {
+            any_name: true,
+            ..self
+        }
+    }
+
     pub fn with_filename<T: Into<Cow<'b, str>>>(self, filename: T) -> Self {
         ContainsPart {
             filename: Some(filename.into()),
@@ -53,6 +138,23 @@ impl<'a, 'b, 'c, 'd> ContainsPart<'a, 'b, 'c, 'd> {
         }
     }
 
+    /// Require the part to have a filename, i.e. to be a file upload.
+    pub fn with_any_filename(self) -> Self {
+        ContainsPart {
+            any_filename: true,
+            ..self
+        }
+    }
+
+    /// Require a filename whose last path component equals `basename`, ignoring Windows and
+    /// POSIX directories sent by legacy clients. See [`Part::basename`].
+    pub fn with_filename_basename<T: Into<Cow<'b, str>>>(self, basename: T) -> Self {
+        ContainsPart {
+            filename_basename: Some(basename.into()),
+            ..self
+        }
+    }
+
     pub fn with_content_type<T: Into<Cow<'c, str>>>(self, content_type: T) -> Self {
         ContainsPart {
             content_type: Some(content_type.into()),
@@ -66,50 +168,199 @@ impl<'a, 'b, 'c, 'd> ContainsPart<'a, 'b, 'c, 'd> {
             ..self
         }
     }
+
+    /// Require the body to be at most `size` bytes long.
+    pub fn with_max_body_size(self, size: usize) -> Self {
+        ContainsPart {
+            max_body_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Require a part header with the given value. Header names are compared case-insensitively.
+    pub fn with_header<N: Into<Cow<'c, str>>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), HeaderCondition::Equals(value.into())));
+        self
+    }
+
+    /// Require a part header with the given bytes as value, for headers that are not valid UTF-8.
+    pub fn with_raw_header<N: Into<Cow<'c, str>>, V: Into<Vec<u8>>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), HeaderCondition::EqualsBytes(value.into())));
+        self
+    }
+
+    /// Require a part header whose value matches the given regular expression. Invalid UTF-8 in
+    /// the value is replaced by `U+FFFD` before matching.
+    ///
+    /// Panics if `regex` is not a valid regular expression.
+    pub fn with_header_matching<N: Into<Cow<'c, str>>>(mut self, name: N, regex: &str) -> Self {
+        let regex = Regex::new(regex).expect("Failed to create regex for part header matcher");
+        self.headers.push((name.into(), HeaderCondition::Matches(regex)));
+        self
+    }
+
+    /// Require the part to have a header with the given name, whatever its value.
+    pub fn with_any_header<N: Into<Cow<'c, str>>>(mut self, name: N) -> Self {
+        self.headers.push((name.into(), HeaderCondition::Present));
+        self
+    }
+
+    /// Require the part to have no header with the given name.
+    pub fn without_header<N: Into<Cow<'c, str>>>(mut self, name: N) -> Self {
+        self.headers.push((name.into(), HeaderCondition::Absent));
+        self
+    }
+
+    /// Require the given `Content-Disposition` type, e.g. `attachment`. The type is compared
+    /// case-insensitively.
+    pub fn with_disposition<T: Into<Cow<'c, str>>>(self, disposition: T) -> Self {
+        ContainsPart {
+            disposition: Some(disposition.into()),
+            ..self
+        }
+    }
+
+    /// Require a `Content-Disposition` parameter with the given value.
+    pub fn with_disposition_param<N: Into<Cow<'c, str>>, V: Into<Cow<'c, str>>>(mut self, name: N, value: V) -> Self {
+        self.disposition_params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Require the body to equal the given text once decoded, see [`Part::text`].
+    pub fn with_text<T: Into<Cow<'d, str>>>(self, text: T) -> Self {
+        ContainsPart {
+            text: Some(text.into()),
+            ..self
+        }
+    }
+
+    /// Normalize line endings and whitespace before comparing text, see [`ContainsPart::with_text`].
+    pub fn with_text_normalization(self, text_normalization: TextNormalization) -> Self {
+        ContainsPart {
+            text_normalization,
+            ..self
+        }
+    }
+
+    /// Compare names and filenames case-insensitively or after Unicode normalization, see
+    /// [`NameNormalization`].
+    pub fn with_name_normalization(self, name_normalization: NameNormalization) -> Self {
+        ContainsPart {
+            name_normalization,
+            ..self
+        }
+    }
+
+    /// Require exactly `count` parts to match instead of at least one.
+    pub fn times(self, count: usize) -> PartCount<'a, 'b, 'c, 'd> {
+        PartCount {
+            part: self,
+            min: count,
+            max: Some(count),
+        }
+    }
+
+    /// Require at most `count` parts to match instead of at least one.
+    pub fn at_most(self, count: usize) -> PartCount<'a, 'b, 'c, 'd> {
+        PartCount {
+            part: self,
+            min: 0,
+            max: Some(count),
+        }
+    }
+}
+
+impl<'a, 'b, 'c, 'd> ContainsPart<'a, 'b, 'c, 'd> {
+    /// Check a single part against the criteria of this matcher.
+    pub fn matches_part(&self, part: &Part) -> bool {
+        self.matches_part_with_charset(part, None)
+    }
+
+    /// Check a single part against the criteria of this matcher, decoding text with the given
+    /// fallback charset if the part doesn't specify one. See [`Part::text_with_fallback`].
+    pub fn matches_part_with_charset(&self, part: &Part, form_charset: Option<&str>) -> bool {
+        let name = self.name.as_ref()
+            .map(|required_name| {
+                part.decoded_name()
+                    .map(|part_name| self.name_normalization.equals(required_name, &part_name))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true)
+            && (!self.any_name || part.raw_disposition_param("name").is_some());
+
+        let filename = self.filename.as_ref()
+            .map(|required_filename| {
+                part.decoded_filename()
+                    .map(|part_filename| self.name_normalization.equals(required_filename, &part_filename))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true)
+            && (!self.any_filename || part.has_file_input());
+
+        let filename_basename = self.filename_basename.as_ref()
+            .map(|required_basename| {
+                part.basename()
+                    .map(|part_basename| self.name_normalization.equals(required_basename, &part_basename))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true);
+
+        let content_type = self.content_type.as_ref()
+            .map(|required_content_type| {
+                part.content_type()
+                    .map(|part_content_type| required_content_type == part_content_type)
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true);
+
+        let body = self.body.as_ref()
+            .map(|required_body| {
+                part.body()
+                    .map(|part_body| required_body.as_ref() == part_body)
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true)
+            && self.max_body_size
+                .map(|max_body_size| part.body().unwrap_or_default().len() <= max_body_size)
+                .unwrap_or(true);
+
+        let headers = self.headers.iter()
+            .all(|(name, condition)| condition.matches_part(name, part));
+
+        let disposition = self.disposition.as_ref()
+            .map(|required_disposition| {
+                part.disposition_type()
+                    .map(|part_disposition| required_disposition.eq_ignore_ascii_case(part_disposition))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true);
+
+        let disposition_params = self.disposition_params.iter()
+            .all(|(name, value)| part.disposition_param(name) == Some(value.as_ref()));
+
+        let text = self.text.as_ref()
+            .map(|required_text| {
+                part.text_with_fallback(form_charset)
+                    .map(|part_text| self.text_normalization.equals(required_text, &part_text))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true);
+
+        name && filename && filename_basename && content_type && body && headers && disposition && disposition_params && text
+    }
 }
 
 impl<'a, 'b, 'c, 'd> Match for ContainsPart<'a, 'b, 'c, 'd> {
     fn matches(&self, request: &Request) -> bool {
-        request.parts().iter()
-            .any(|part| {
-                let name = self.name.as_ref()
-                    .map(|required_name| {
-                        part.name()
-                            .map(|part_name| required_name == part_name)
-                            .unwrap_or(false)
-                    })
-                    .unwrap_or(true);
-
-                let filename = self.filename.as_ref()
-                    .map(|required_filename| {
-                        part.filename()
-                            .map(|part_filename| required_filename == part_filename)
-                            .unwrap_or(false)
-                    })
-                    .unwrap_or(true);
-
-                let content_type = self.content_type.as_ref()
-                    .map(|required_content_type| {
-                        part.content_type()
-                            .map(|part_content_type| required_content_type == part_content_type)
-                            .unwrap_or(false)
-                    })
-                    .unwrap_or(true);
-
-                let body = self.body.as_ref()
-                    .map(|required_body| {
-                        part.body()
-                            .map(|part_body| required_body.as_ref() == part_body)
-                            .unwrap_or(false)
-                    })
-                    .unwrap_or(true);
-
-                name && filename && content_type && body
-            })
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        parts.iter()
+            .any(|part| self.matches_part_with_charset(part, form_charset))
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use indoc::indoc;
     use maplit::hashmap;
@@ -122,7 +373,22 @@ mod tests {
     fn default_should_be_all_none() {
         assert_eq!(
             ContainsPart::default(),
-            ContainsPart { name: None, filename: None, content_type: None, body: None }
+            ContainsPart {
+                name: None,
+                any_name: false,
+                filename: None,
+                any_filename: false,
+                filename_basename: None,
+                content_type: None,
+                body: None,
+                max_body_size: None,
+                headers: vec![],
+                disposition: None,
+                disposition_params: vec![],
+                text: None,
+                text_normalization: TextNormalization::default(),
+                name_normalization: NameNormalization::default(),
+            }
         );
     }
 
@@ -156,6 +422,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_add_filename_basename() {
+        assert_eq!(
+            ContainsPart::new().with_filename_basename("report.pdf"),
+            ContainsPart {
+                filename_basename: Some("report.pdf".into()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_add_presence_and_size_criteria() {
+        assert_eq!(
+            ContainsPart::new()
+                .with_any_name()
+                .with_any_filename()
+                .with_any_header("Content-Type")
+                .with_max_body_size(1024),
+            ContainsPart {
+                any_name: true,
+                any_filename: true,
+                headers: vec![("Content-Type".into(), HeaderCondition::Present)],
+                max_body_size: Some(1024),
+                ..Default::default()
+            }
+        );
+    }
+
     #[test]
     fn should_add_content_type() {
         assert_eq!(
@@ -356,4 +651,236 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_add_header_conditions() {
+        assert_eq!(
+            ContainsPart::new()
+                .with_header("Content-ID", "<a@b>")
+                .with_raw_header("X-Filename", b"caf\xe9.txt".to_vec())
+                .with_header_matching("X-Checksum", "^[0-9a-f]+$")
+                .without_header("Content-Transfer-Encoding"),
+            ContainsPart {
+                headers: vec![
+                    ("Content-ID".into(), HeaderCondition::Equals("<a@b>".into())),
+                    ("X-Filename".into(), HeaderCondition::EqualsBytes(b"caf\xe9.txt".to_vec())),
+                    ("X-Checksum".into(), HeaderCondition::Matches(Regex::new("^[0-9a-f]+$").unwrap())),
+                    ("Content-Transfer-Encoding".into(), HeaderCondition::Absent),
+                ],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_match_on_headers() {
+        let request = requestb(
+            multipart_header(),
+            indoc!{r#"
+            --xyz
+            Content-Disposition: form-data; name="part-a"
+            content-id: <part-a@example.com>
+            X-Checksum: 9a0364b9e99bb480dd25e1f0284c8555
+
+            content
+            --xyz--
+        "#}.as_bytes().into()
+        );
+
+        assert!(ContainsPart::new().with_header("Content-ID", "<part-a@example.com>").matches(&request));
+        assert!(!ContainsPart::new().with_header("Content-ID", "<part-b@example.com>").matches(&request));
+
+        assert!(ContainsPart::new().with_header_matching("x-checksum", "^[0-9a-f]{32}$").matches(&request));
+        assert!(!ContainsPart::new().with_header_matching("x-checksum", "^[0-9a-f]{40}$").matches(&request));
+
+        assert!(ContainsPart::new().without_header("Content-Transfer-Encoding").matches(&request));
+        assert!(!ContainsPart::new().without_header("X-CHECKSUM").matches(&request));
+    }
+
+    #[test]
+    fn should_match_on_latin1_headers() {
+        let mut body = b"--xyz\r\nContent-Disposition: form-data; name=\"file\"; filename=\"caf\xe9.txt\"\r\n".to_vec();
+        body.extend_from_slice(b"Content-Type: text/plain\r\n\r\ncontent\r\n--xyz--\r\n");
+        let request = requestb(multipart_header(), body);
+
+        assert!(ContainsPart::new().with_content_type("text/plain").matches(&request));
+        assert!(ContainsPart::new().with_name("file").with_filename("café.txt").matches(&request));
+        assert!(ContainsPart::new()
+            .with_raw_header("content-disposition", b"form-data; name=\"file\"; filename=\"caf\xe9.txt\"".to_vec())
+            .matches(&request));
+        assert!(ContainsPart::new().with_header_matching("Content-Disposition", "caf\u{FFFD}").matches(&request));
+        assert!(!ContainsPart::new().without_header("Content-Disposition").matches(&request));
+    }
+
+    #[test]
+    fn should_add_disposition() {
+        assert_eq!(
+            ContainsPart::new()
+                .with_disposition("attachment")
+                .with_disposition_param("creation-date", "Wed, 12 Feb 1997 16:29:51 -0500"),
+            ContainsPart {
+                disposition: Some("attachment".into()),
+                disposition_params: vec![("creation-date".into(), "Wed, 12 Feb 1997 16:29:51 -0500".into())],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_match_on_disposition() {
+        let request = requestb(
+            hashmap!{
+                name("content-type") => values("multipart/mixed; boundary=xyz"),
+            },
+            indoc!{r#"
+            --xyz
+            Content-Type: text/plain
+
+            Please find the report attached.
+            --xyz
+            Content-Disposition: attachment; filename="report.pdf"; creation-date="Wed, 12 Feb 1997 16:29:51 -0500"
+            Content-Type: application/pdf
+
+            %PDF-1.4
+            --xyz--
+        "#}.as_bytes().into()
+        );
+
+        assert!(ContainsPart::new().with_disposition("attachment").with_filename("report.pdf").matches(&request));
+        assert!(ContainsPart::new().with_disposition("ATTACHMENT").matches(&request));
+        assert!(!ContainsPart::new().with_disposition("inline").matches(&request));
+        assert!(!ContainsPart::new().with_disposition("form-data").matches(&request));
+
+        assert!(ContainsPart::new()
+            .with_disposition_param("creation-date", "Wed, 12 Feb 1997 16:29:51 -0500")
+            .matches(&request));
+        assert!(!ContainsPart::new()
+            .with_disposition_param("creation-date", "Thu, 13 Feb 1997 16:29:51 -0500")
+            .matches(&request));
+    }
+
+    #[test]
+    fn should_add_text() {
+        assert_eq!(
+            ContainsPart::new().with_text("grüße"),
+            ContainsPart {
+                text: Some("grüße".into()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_match_on_text() {
+        let request = requestb(
+            multipart_header(),
+            indoc!{r#"
+            --xyz
+            Content-Disposition: form-data; name="greeting"
+            Content-Type: text/plain; charset=utf-8
+
+            grüße
+            --xyz--
+        "#}.as_bytes().into()
+        );
+
+        assert!(ContainsPart::new().with_text("grüße").matches(&request));
+        assert!(!ContainsPart::new().with_text("grusse").matches(&request));
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn should_match_on_text_using_form_charset() {
+        let mut body = b"--xyz\r\nContent-Disposition: form-data; name=\"_charset_\"\r\n\r\nwindows-1252\r\n".to_vec();
+        body.extend_from_slice(b"--xyz\r\nContent-Disposition: form-data; name=\"greeting\"\r\n\r\ngr\xfc\xdfe\r\n--xyz--\r\n");
+        let request = requestb(multipart_header(), body);
+
+        assert!(ContainsPart::new().with_name("greeting").with_text("grüße").matches(&request));
+        assert!(ContainsPart::new().with_text("grüße").times(1).matches(&request));
+    }
+
+    #[test]
+    fn should_match_on_normalized_text() {
+        let request = requestb(
+            multipart_header(),
+            "--xyz\r\nContent-Disposition: form-data; name=\"comment\"\r\n\r\nfirst line \r\nsecond line\r\n--xyz--\r\n"
+                .as_bytes()
+                .into(),
+        );
+
+        assert!(!ContainsPart::new().with_text("first line\nsecond line").matches(&request));
+        assert!(!ContainsPart::new()
+            .with_text("first line\nsecond line")
+            .with_text_normalization(TextNormalization::new().line_endings())
+            .matches(&request));
+        assert!(ContainsPart::new()
+            .with_text("first line\nsecond line")
+            .with_text_normalization(TextNormalization::new().line_endings().trim_trailing_whitespace())
+            .matches(&request));
+        assert!(ContainsPart::new()
+            .with_text("first line second line")
+            .with_text_normalization(TextNormalization::new().collapse_whitespace())
+            .matches(&request));
+    }
+
+    #[test]
+    fn should_match_on_decoded_name_and_filename() {
+        let request = requestb(
+            multipart_header(),
+            indoc!{r#"
+            --xyz
+            Content-Disposition: form-data; name="say \"hi\""; filename="%22quoted%22.txt"
+
+            hi
+            --xyz--
+        "#}.as_bytes().into()
+        );
+
+        assert!(ContainsPart::new().with_name("say \"hi\"").matches(&request));
+        assert!(ContainsPart::new().with_filename("\"quoted\".txt").matches(&request));
+        assert!(!ContainsPart::new().with_filename("%22quoted%22.txt").matches(&request));
+    }
+
+    #[test]
+    fn should_match_on_filename_basename() {
+        let request = requestb(
+            multipart_header(),
+            indoc!{r#"
+            --xyz
+            Content-Disposition: form-data; name="file"; filename="C:\Users\me\Desktop\report.pdf"
+
+            %PDF-1.4
+            --xyz--
+        "#}.as_bytes().into()
+        );
+
+        assert!(ContainsPart::new().with_filename_basename("report.pdf").matches(&request));
+        assert!(!ContainsPart::new().with_filename_basename("Desktop").matches(&request));
+        assert!(!ContainsPart::new().with_filename("report.pdf").matches(&request));
+    }
+
+    #[test]
+    fn should_match_on_normalized_name_and_filename() {
+        let request = requestb(
+            multipart_header(),
+            "--xyz\r\nContent-Disposition: form-data; name=\"Avatar\"; filename=\"cafe\u{301}.txt\"\r\n\r\ncontent\r\n--xyz--\r\n"
+                .as_bytes()
+                .into(),
+        );
+
+        assert!(!ContainsPart::new().with_name("avatar").matches(&request));
+        assert!(ContainsPart::new()
+            .with_name("avatar")
+            .with_name_normalization(NameNormalization::new().ignore_ascii_case())
+            .matches(&request));
+
+        assert!(!ContainsPart::new().with_filename("café.txt").matches(&request));
+        assert!(ContainsPart::new()
+            .with_filename("café.txt")
+            .with_name_normalization(NameNormalization::new().nfc())
+            .matches(&request));
+        assert!(ContainsPart::new()
+            .with_filename_basename("café.txt")
+            .with_name_normalization(NameNormalization::new().nfd())
+            .matches(&request));
+    }
 }
@@ -0,0 +1,122 @@
+use std::borrow::Cow;
+
+use wiremock::{Match, Request};
+
+use crate::matchers::ContainsPart;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart request that has no part matching the given criteria.
+///
+/// Unlike a plain negation of [`ContainsPart`], requests that aren't multipart don't match.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(DoesNotContainPart(ContainsPart::new().with_filename("debug.log")))
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct DoesNotContainPart<'a, 'b, 'c, 'd>(pub ContainsPart<'a, 'b, 'c, 'd>);
+
+impl<'a, 'b, 'c, 'd> From<ContainsPart<'a, 'b, 'c, 'd>> for DoesNotContainPart<'a, 'b, 'c, 'd> {
+    fn from(spec: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        DoesNotContainPart(spec)
+    }
+}
+
+impl<'a, 'b, 'c, 'd> Match for DoesNotContainPart<'a, 'b, 'c, 'd> {
+    fn matches(&self, request: &Request) -> bool {
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        request.is_multipart()
+            && !parts.iter().any(|part| self.0.matches_part_with_charset(part, form_charset))
+    }
+}
+
+/// Match a multipart request that has no part with this name.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoPartNamed<'a>(pub Cow<'a, str>);
+
+impl<'a> NoPartNamed<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(name: T) -> Self {
+        NoPartNamed(name.into())
+    }
+}
+
+impl<'a> Match for NoPartNamed<'a> {
+    fn matches(&self, request: &Request) -> bool {
+        request.is_multipart()
+            && !request.parts().iter().any(|part| part.decoded_name().as_deref() == Some(self.0.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+
+    use crate::test_utils::{form_request, name, requestb, values};
+
+    use super::*;
+
+    #[test]
+    fn does_not_contain_part_should_match_if_no_part_matches() {
+        let request = form_request()
+            .field("username", "rene")
+            .file("avatar", "me.png", "image/png", "png")
+            .build();
+
+        assert!(DoesNotContainPart(ContainsPart::new().with_name("password")).matches(&request));
+        assert!(DoesNotContainPart(ContainsPart::new().with_name("avatar").with_content_type("image/jpeg")).matches(&request));
+    }
+
+    #[test]
+    fn does_not_contain_part_should_not_match_if_a_part_matches() {
+        let request = form_request()
+            .field("username", "rene")
+            .file("avatar", "me.png", "image/png", "png")
+            .build();
+
+        assert!(!DoesNotContainPart(ContainsPart::new().with_name("username")).matches(&request));
+        assert!(!DoesNotContainPart::from(ContainsPart::new().with_filename("me.png")).matches(&request));
+    }
+
+    #[test]
+    fn does_not_contain_part_should_not_match_non_multipart_request() {
+        let request = requestb(
+            hashmap!{
+                name("content-type") => values("text/plain"),
+            },
+            "password".as_bytes().into(),
+        );
+
+        assert!(!DoesNotContainPart(ContainsPart::new().with_name("password")).matches(&request));
+    }
+
+    #[test]
+    fn no_part_named_should_check_names() {
+        let request = form_request().field("username", "rene").build();
+        let not_multipart = requestb(
+            hashmap!{
+                name("content-type") => values("text/plain"),
+            },
+            "password".as_bytes().into(),
+        );
+
+        assert!(NoPartNamed::new("password").matches(&request));
+        assert!(!NoPartNamed::new("username").matches(&request));
+        assert!(!NoPartNamed::new("password").matches(&not_multipart));
+    }
+}
@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+
+use wiremock::{Match, Request};
+
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart/form-data request with a file field for which no file was chosen.
+///
+/// Browsers send such a field as a part with an empty filename and an empty body, see
+/// [`Part::is_empty_file_input`](crate::Part::is_empty_file_input).
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(EmptyFileField::new("avatar"))
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct EmptyFileField<'a>(pub Cow<'a, str>);
+
+impl<'a> EmptyFileField<'a> {
+    pub fn new<T: Into<Cow<'a, str>>>(name: T) -> Self {
+        EmptyFileField(name.into())
+    }
+}
+
+impl<'a> Match for EmptyFileField<'a> {
+    fn matches(&self, request: &Request) -> bool {
+        request.parts().iter()
+            .any(|part| part.decoded_name().as_deref() == Some(self.0.as_ref()) && part.is_empty_file_input())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{multipart_header, requestb};
+
+    use super::*;
+
+    #[test]
+    fn should_match_file_field_without_file() {
+        let request = requestb(
+            multipart_header(),
+            "--xyz\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"\"\r\nContent-Type: application/octet-stream\r\n\r\n\r\n--xyz--\r\n"
+                .as_bytes()
+                .into(),
+        );
+
+        assert!(EmptyFileField::new("avatar").matches(&request));
+        assert!(!EmptyFileField::new("resume").matches(&request));
+    }
+
+    #[test]
+    fn should_not_match_file_field_with_file() {
+        let request = requestb(
+            multipart_header(),
+            "--xyz\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"me.png\"\r\n\r\npng\r\n--xyz--\r\n"
+                .as_bytes()
+                .into(),
+        );
+
+        assert!(!EmptyFileField::new("avatar").matches(&request));
+    }
+
+    #[test]
+    fn should_not_match_text_field() {
+        let request = requestb(
+            multipart_header(),
+            "--xyz\r\nContent-Disposition: form-data; name=\"avatar\"\r\n\r\n\r\n--xyz--\r\n"
+                .as_bytes()
+                .into(),
+        );
+
+        assert!(!EmptyFileField::new("avatar").matches(&request));
+    }
+}
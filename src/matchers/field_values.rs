@@ -0,0 +1,189 @@
+use wiremock::{Match, Request};
+
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+use crate::text::TextNormalization;
+
+/// Match the values of a repeated field, like `tags` sent three times or `files[]`.
+///
+/// The bodies of all parts with the given name are decoded as text (see
+/// [`Part::text_with_fallback`]) and compared with the expected values. By default the values
+/// have to appear in the given order; use `any_order()` to compare them regardless of order.
+/// Either way, no additional values are allowed.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(FieldValues::new("tags", ["a", "b", "c"]))
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct FieldValues {
+    pub name: String,
+    pub values: Vec<String>,
+    pub ordered: bool,
+    pub text_normalization: TextNormalization,
+}
+
+impl Default for FieldValues {
+    /// No values for an empty name, compared in order like [`FieldValues::new`].
+    fn default() -> Self {
+        FieldValues {
+            name: String::new(),
+            values: vec![],
+            ordered: true,
+            text_normalization: TextNormalization::default(),
+        }
+    }
+}
+
+impl FieldValues {
+    pub fn new<N, I, V>(name: N, values: I) -> Self
+        where N: Into<String>,
+              I: IntoIterator<Item = V>,
+              V: Into<String>,
+    {
+        FieldValues {
+            name: name.into(),
+            values: values.into_iter().map(Into::into).collect(),
+            ordered: true,
+            text_normalization: TextNormalization::default(),
+        }
+    }
+
+    pub fn in_order(self) -> Self {
+        FieldValues {
+            ordered: true,
+            ..self
+        }
+    }
+
+    pub fn any_order(self) -> Self {
+        FieldValues {
+            ordered: false,
+            ..self
+        }
+    }
+
+    /// Normalize line endings and whitespace before comparing values.
+    pub fn with_text_normalization(self, text_normalization: TextNormalization) -> Self {
+        FieldValues {
+            text_normalization,
+            ..self
+        }
+    }
+}
+
+impl Match for FieldValues {
+    fn matches(&self, request: &Request) -> bool {
+        if !request.is_multipart() {
+            return false;
+        }
+
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        let actual = Part::group_by_name(&parts)
+            .into_iter()
+            .find(|(name, _)| *name == self.name)
+            .map(|(_, group)| {
+                group.iter()
+                    .map(|part| {
+                        part.text_with_fallback(form_charset)
+                            .map(|text| self.text_normalization.apply(&text).into_owned())
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .unwrap_or_else(|| Some(vec![]));
+
+        let mut expected = self.values.iter()
+            .map(|value| self.text_normalization.apply(value).into_owned())
+            .collect::<Vec<_>>();
+
+        match actual {
+            None => false,
+            Some(mut actual) => {
+                if !self.ordered {
+                    actual.sort_unstable();
+                    expected.sort_unstable();
+                }
+                actual == expected
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    #[test]
+    fn default_should_compare_in_order() {
+        assert_eq!(FieldValues::default(), FieldValues::new("", Vec::<String>::new()));
+        assert!(FieldValues::default().ordered);
+    }
+
+    #[test]
+    fn should_match_values_in_order() {
+        let request = form_request()
+            .field("tags", "red")
+            .field("title", "The title")
+            .field("tags", "green")
+            .file("files[]", "a.txt", "text/plain", "first file")
+            .field("tags", "blue")
+            .file("files[]", "b.txt", "text/plain", "second file")
+            .build();
+
+        assert!(FieldValues::new("tags", ["red", "green", "blue"]).matches(&request));
+        assert!(FieldValues::new("files[]", ["first file", "second file"]).matches(&request));
+        assert!(!FieldValues::new("tags", ["blue", "green", "red"]).in_order().matches(&request));
+    }
+
+    #[test]
+    fn should_match_values_in_any_order() {
+        let request = form_request()
+            .field("tags", "red")
+            .field("tags", "green")
+            .field("tags", "blue")
+            .build();
+
+        assert!(FieldValues::new("tags", ["blue", "green", "red"]).any_order().matches(&request));
+        assert!(!FieldValues::new("tags", ["blue", "green"]).any_order().matches(&request));
+    }
+
+    #[test]
+    fn should_not_allow_missing_or_extra_values() {
+        let request = form_request()
+            .field("tags", "red")
+            .field("tags", "green")
+            .field("tags", "blue")
+            .build();
+
+        assert!(!FieldValues::new("tags", ["red", "green"]).matches(&request));
+        assert!(!FieldValues::new("tags", ["red", "green", "blue", "black"]).matches(&request));
+        assert!(!FieldValues::new("colors", ["red"]).matches(&request));
+    }
+
+    #[test]
+    fn should_compare_normalized_values() {
+        let request = form_request().field("lines", "a\r\nb").build();
+
+        assert!(!FieldValues::new("lines", ["a\nb"]).matches(&request));
+        assert!(FieldValues::new("lines", ["a\nb"])
+            .with_text_normalization(TextNormalization::new().line_endings())
+            .matches(&request));
+    }
+}
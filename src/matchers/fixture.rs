@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use base64::Engine;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use wiremock::{Match, Request};
+
+use crate::matchers::PartSpec;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Matcher built from a declarative YAML or JSON description of the expected parts.
+///
+/// A fixture lists the expected parts in the format of [`PartSpec`]. Besides a literal `body`,
+/// bodies can be given base64-encoded (`body_base64`) or as a path to a file (`body_file`), which
+/// is resolved relative to the fixture file and read when loading. Each part has to be present
+/// at least once, or exactly `times` times, or at most `at_most` times.
+///
+/// ```yaml
+/// number_of_parts: 2   # optional, total number of parts in the request
+/// ordered: true        # optional, the listed parts appear in this order
+/// exact: false         # optional, every part of the request must match a listed part
+/// parts:
+///   - name: metadata
+///     content_type: application/json
+///     text: '{"title": "Holiday"}'
+///   - name: photo
+///     filename: beach.jpg
+///     body_file: beach.jpg
+///     headers:
+///       Content-Transfer-Encoding: {equals: binary}
+///       Content-ID: absent
+/// ```
+///
+/// Only available with the `fixtures` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     let fixture = MultipartFixture::from_yaml_str(r#"
+///         parts:
+///           - name: avatar
+///             content_type: image/png
+///             body_base64: iVBORw0KGgo=
+///     "#).unwrap();
+///
+///     Mock::given(method("POST"))
+///         .and(fixture)
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct MultipartFixture {
+    pub parts: Vec<FixturePart>,
+    pub number_of_parts: Option<usize>,
+    pub ordered: bool,
+    pub exact: bool,
+}
+
+/// An expected part of a [`MultipartFixture`] and how often it has to be present.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FixturePart {
+    pub spec: PartSpec,
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+/// Error returned when a fixture cannot be read or doesn't describe valid expectations.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FixtureError(String);
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for FixtureError {}
+
+impl MultipartFixture {
+    /// Load a fixture file. Files ending in `.json` are parsed as JSON, all others as YAML.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, FixtureError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|error| FixtureError(format!("cannot read fixture {}: {}", path.display(), error)))?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        let is_json = path.extension().map(|extension| extension.eq_ignore_ascii_case("json")).unwrap_or(false);
+        let document = if is_json {
+            serde_json::from_str(&content).map_err(|error| FixtureError(format!("invalid fixture {}: {}", path.display(), error)))?
+        } else {
+            serde_yaml::from_str(&content).map_err(|error| FixtureError(format!("invalid fixture {}: {}", path.display(), error)))?
+        };
+        Self::from_document(document, base_dir)
+    }
+
+    /// Parse a YAML fixture. Body files are resolved relative to the working directory.
+    pub fn from_yaml_str(yaml: &str) -> Result<Self, FixtureError> {
+        let document = serde_yaml::from_str(yaml)
+            .map_err(|error| FixtureError(format!("invalid fixture: {}", error)))?;
+        Self::from_document(document, Path::new(""))
+    }
+
+    /// Parse a JSON fixture. Body files are resolved relative to the working directory.
+    pub fn from_json_str(json: &str) -> Result<Self, FixtureError> {
+        let document = serde_json::from_str(json)
+            .map_err(|error| FixtureError(format!("invalid fixture: {}", error)))?;
+        Self::from_document(document, Path::new(""))
+    }
+
+    fn from_document(document: FixtureDocument, base_dir: &Path) -> Result<Self, FixtureError> {
+        let parts = document.parts.into_iter()
+            .enumerate()
+            .map(|(index, part)| part.resolve(base_dir).map_err(|error| FixtureError(format!("part {}: {}", index, error))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(MultipartFixture {
+            parts,
+            number_of_parts: document.number_of_parts,
+            ordered: document.ordered,
+            exact: document.exact,
+        })
+    }
+
+    /// Whether the listed parts appear in the given order, each matched by a later part of the
+    /// request than the previous one.
+    fn in_order(&self, parts: &[Part], form_charset: Option<&str>) -> bool {
+        let mut remaining = parts.iter();
+        self.parts.iter().all(|expected| {
+            let spec = expected.spec.as_contains_part();
+            remaining.any(|part| spec.matches_part_with_charset(part, form_charset))
+        })
+    }
+}
+
+impl Match for MultipartFixture {
+    fn matches(&self, request: &Request) -> bool {
+        if !request.is_multipart() {
+            return false;
+        }
+
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        let specs = self.parts.iter()
+            .map(|expected| expected.spec.as_contains_part())
+            .collect::<Vec<_>>();
+
+        let number_of_parts = self.number_of_parts
+            .map(|count| parts.len() == count)
+            .unwrap_or(true);
+
+        let counts = self.parts.iter().zip(&specs).all(|(expected, spec)| {
+            let count = parts.iter()
+                .filter(|part| spec.matches_part_with_charset(part, form_charset))
+                .count();
+            count >= expected.min && expected.max.map(|max| count <= max).unwrap_or(true)
+        });
+
+        let exact = !self.exact || parts.iter()
+            .all(|part| specs.iter().any(|spec| spec.matches_part_with_charset(part, form_charset)));
+
+        let ordered = !self.ordered || self.in_order(&parts, form_charset);
+
+        number_of_parts && counts && exact && ordered
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FixtureDocument {
+    #[serde(default)]
+    parts: Vec<FixturePartDocument>,
+    number_of_parts: Option<usize>,
+    #[serde(default)]
+    ordered: bool,
+    #[serde(default)]
+    exact: bool,
+}
+
+#[derive(Deserialize)]
+struct FixturePartDocument {
+    #[serde(flatten)]
+    spec: PartSpec,
+    body_base64: Option<String>,
+    body_file: Option<String>,
+    times: Option<usize>,
+    at_most: Option<usize>,
+    /// Collects the fields not consumed by `spec`, as `deny_unknown_fields` doesn't apply to
+    /// flattened structs.
+    #[serde(flatten)]
+    unknown: BTreeMap<String, IgnoredAny>,
+}
+
+impl FixturePartDocument {
+    fn resolve(self, base_dir: &Path) -> Result<FixturePart, String> {
+        if let Some(field) = self.unknown.keys().next() {
+            return Err(format!("unknown field `{}`", field));
+        }
+
+        let body = match (self.spec.body, self.body_base64, self.body_file) {
+            (body, None, None) => body,
+            (None, Some(encoded), None) => Some(
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .map_err(|error| format!("invalid body_base64: {}", error))?,
+            ),
+            (None, None, Some(file)) => {
+                let path = base_dir.join(file);
+                Some(std::fs::read(&path).map_err(|error| format!("cannot read {}: {}", path.display(), error))?)
+            },
+            _ => return Err("only one of body, body_base64 and body_file may be given".to_string()),
+        };
+
+        let (min, max) = match (self.times, self.at_most) {
+            (Some(_), Some(_)) => return Err("only one of times and at_most may be given".to_string()),
+            (Some(times), None) => (times, Some(times)),
+            (None, Some(at_most)) => (0, Some(at_most)),
+            (None, None) => (1, None),
+        };
+
+        Ok(FixturePart { spec: PartSpec { body, ..self.spec }, min, max })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    #[test]
+    fn should_match_yaml_fixture() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"metadata\"", "Content-Type: application/json"],
+                r#"{"title": "Holiday"}"#,
+            )
+            .part(
+                &["Content-Disposition: form-data; name=\"photo\"; filename=\"beach.jpg\"", "Content-Transfer-Encoding: binary"],
+                "jpeg",
+            )
+            .file("photo", "sunset.jpg", "image/jpeg", "jpeg")
+            .build();
+
+        let fixture = MultipartFixture::from_yaml_str(indoc!{r#"
+            number_of_parts: 3
+            parts:
+              - name: metadata
+                content_type: application/json
+                text: '{"title": "Holiday"}'
+              - name: photo
+                body_base64: anBlZw==
+                times: 2
+              - filename: beach.jpg
+                headers:
+                  content-transfer-encoding: {equals: binary}
+        "#}).unwrap();
+
+        assert_eq!(fixture.parts[1].spec.body.as_deref(), Some("jpeg".as_bytes()));
+        assert!(fixture.matches(&request));
+    }
+
+    #[test]
+    fn should_match_json_fixture() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"metadata\"", "Content-Type: application/json"],
+                r#"{"title": "Holiday"}"#,
+            )
+            .part(
+                &["Content-Disposition: form-data; name=\"photo\"; filename=\"beach.jpg\"", "Content-Transfer-Encoding: binary"],
+                "jpeg",
+            )
+            .file("photo", "sunset.jpg", "image/jpeg", "jpeg")
+            .build();
+
+        let fixture = MultipartFixture::from_json_str(r#"{
+            "parts": [
+                {"name": "photo", "filename": "sunset.jpg", "headers": {"Content-Transfer-Encoding": "absent"}},
+                {"name": "avatar", "at_most": 0}
+            ]
+        }"#).unwrap();
+
+        assert!(fixture.matches(&request));
+    }
+
+    #[test]
+    fn should_check_counts() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"metadata\"", "Content-Type: application/json"],
+                r#"{"title": "Holiday"}"#,
+            )
+            .part(
+                &["Content-Disposition: form-data; name=\"photo\"; filename=\"beach.jpg\"", "Content-Transfer-Encoding: binary"],
+                "jpeg",
+            )
+            .file("photo", "sunset.jpg", "image/jpeg", "jpeg")
+            .build();
+
+        assert!(!MultipartFixture::from_yaml_str("parts: [{name: photo, times: 1}]").unwrap().matches(&request));
+        assert!(!MultipartFixture::from_yaml_str("parts: [{name: avatar}]").unwrap().matches(&request));
+        assert!(!MultipartFixture::from_yaml_str("number_of_parts: 2").unwrap().matches(&request));
+    }
+
+    #[test]
+    fn should_check_order() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"metadata\"", "Content-Type: application/json"],
+                r#"{"title": "Holiday"}"#,
+            )
+            .part(
+                &["Content-Disposition: form-data; name=\"photo\"; filename=\"beach.jpg\"", "Content-Transfer-Encoding: binary"],
+                "jpeg",
+            )
+            .file("photo", "sunset.jpg", "image/jpeg", "jpeg")
+            .build();
+
+        let in_order = "ordered: true\nparts: [{name: metadata}, {filename: sunset.jpg}]";
+        let out_of_order = "ordered: true\nparts: [{filename: sunset.jpg}, {name: metadata}]";
+
+        assert!(MultipartFixture::from_yaml_str(in_order).unwrap().matches(&request));
+        assert!(!MultipartFixture::from_yaml_str(out_of_order).unwrap().matches(&request));
+        assert!(MultipartFixture::from_yaml_str("parts: [{filename: sunset.jpg}, {name: metadata}]").unwrap().matches(&request));
+    }
+
+    #[test]
+    fn should_check_for_unexpected_parts() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"metadata\"", "Content-Type: application/json"],
+                r#"{"title": "Holiday"}"#,
+            )
+            .part(
+                &["Content-Disposition: form-data; name=\"photo\"; filename=\"beach.jpg\"", "Content-Transfer-Encoding: binary"],
+                "jpeg",
+            )
+            .file("photo", "sunset.jpg", "image/jpeg", "jpeg")
+            .build();
+
+        assert!(!MultipartFixture::from_yaml_str("exact: true\nparts: [{name: photo}]").unwrap().matches(&request));
+        assert!(MultipartFixture::from_yaml_str("exact: true\nparts: [{name: photo}, {name: metadata}]").unwrap().matches(&request));
+    }
+
+    #[test]
+    fn should_load_fixture_file_with_body_file() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"metadata\"", "Content-Type: application/json"],
+                r#"{"title": "Holiday"}"#,
+            )
+            .part(
+                &["Content-Disposition: form-data; name=\"photo\"; filename=\"beach.jpg\"", "Content-Transfer-Encoding: binary"],
+                "jpeg",
+            )
+            .file("photo", "sunset.jpg", "image/jpeg", "jpeg")
+            .build();
+
+        let dir = std::env::temp_dir().join(format!("wiremock-multipart-fixture-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("photo.jpg"), "jpeg").unwrap();
+        std::fs::write(dir.join("upload.yaml"), "parts: [{filename: beach.jpg, body_file: photo.jpg}]").unwrap();
+        std::fs::write(dir.join("upload.json"), r#"{"parts": [{"filename": "beach.jpg", "body_file": "missing.jpg"}]}"#).unwrap();
+
+        let fixture = MultipartFixture::load(dir.join("upload.yaml"));
+        let missing = MultipartFixture::load(dir.join("upload.json"));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(fixture.unwrap().matches(&request));
+        assert!(missing.unwrap_err().to_string().starts_with("part 0: cannot read"));
+    }
+
+    #[test]
+    fn should_reject_invalid_fixtures() {
+        assert_eq!(
+            MultipartFixture::from_yaml_str("parts: [{body: a, body_base64: YQ==}]").unwrap_err().to_string(),
+            "part 0: only one of body, body_base64 and body_file may be given",
+        );
+        assert!(MultipartFixture::from_yaml_str("parts: [{nmae: typo}]").unwrap_err().to_string().contains("nmae"));
+        assert!(MultipartFixture::from_json_str("parts").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use serde::Serialize;
+use wiremock::{Match, Request};
+
+use crate::form::{self, FormError, FormValue};
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart/form-data request whose fields equal a serialized value.
+///
+/// The value is serialized with the same conventions [`RequestUtils::form`] uses for
+/// deserialization: scalars become text fields, sequences become repeated fields in the given
+/// order, `None` fields and empty sequences have to be absent and
+/// [`UploadedFile`](crate::UploadedFile)s have to be sent as file parts. The request must not
+/// contain any other parts.
+///
+/// Fields are decoded with the charset of their part or the form's `_charset_` field. Numbers,
+/// booleans, chars and unit enum variants are parsed from the trimmed text and compared by value,
+/// so `1.0` and ` 1` both match `1.0_f64`. Strings have to match exactly.
+///
+/// Only available with the `serde` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[derive(Serialize)]
+/// struct Signup {
+///     username: String,
+///     age: u32,
+///     newsletter: bool,
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(FormEquals::new(&Signup {
+///             username: "jane".into(),
+///             age: 34,
+///             newsletter: false,
+///         }))
+///         .respond_with(ResponseTemplate::new(201))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct FormEquals {
+    fields: Vec<(String, Vec<FormValue>)>,
+}
+
+impl FormEquals {
+    /// Create the matcher from a serializable value.
+    ///
+    /// Panics if the value cannot be represented as form fields, e.g. because it contains
+    /// nested structs. Use [`FormEquals::try_new`] to handle that case.
+    pub fn new<T: Serialize + ?Sized>(value: &T) -> Self {
+        match Self::try_new(value) {
+            Ok(matcher) => matcher,
+            Err(error) => panic!("cannot create FormEquals matcher: {}", error),
+        }
+    }
+
+    pub fn try_new<T: Serialize + ?Sized>(value: &T) -> Result<Self, FormError> {
+        Ok(FormEquals {
+            fields: form::to_fields(value)?,
+        })
+    }
+}
+
+impl Match for FormEquals {
+    fn matches(&self, request: &Request) -> bool {
+        if !request.is_multipart() {
+            return false;
+        }
+
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        let groups = Part::group_by_name(&parts);
+        let named_parts = groups.iter().map(|(_, group)| group.len()).sum::<usize>();
+        if groups.len() != self.fields.len() || named_parts != parts.len() {
+            return false;
+        }
+
+        self.fields.iter().all(|(name, expected)| {
+            groups.iter()
+                .find(|(group_name, _)| group_name == name)
+                .map(|(_, group)| {
+                    group.len() == expected.len()
+                        && expected.iter()
+                            .zip(group.iter())
+                            .all(|(value, part)| value.matches_part(part, form::fallback_charset(name, form_charset)))
+                })
+                .unwrap_or(false)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use crate::test_utils::form_request;
+    use crate::UploadedFile;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Visibility {
+        Private,
+    }
+
+    #[derive(Serialize)]
+    struct Upload {
+        title: String,
+        count: u32,
+        draft: bool,
+        visibility: Visibility,
+        tags: Vec<&'static str>,
+        description: Option<String>,
+        attachment: UploadedFile,
+    }
+
+    fn upload() -> Upload {
+        Upload {
+            title: "Holiday".into(),
+            count: 42,
+            draft: true,
+            visibility: Visibility::Private,
+            tags: vec!["beach", "sunset"],
+            description: None,
+            attachment: UploadedFile {
+                filename: "notes.txt".into(),
+                content_type: Some("text/plain".into()),
+                bytes: b"some notes".to_vec(),
+            },
+        }
+    }
+
+    fn request(tags: [&str; 2]) -> Request {
+        form_request()
+            .field("title", "Holiday")
+            .field("count", "42")
+            .field("draft", "true")
+            .field("visibility", "private")
+            .field("tags", tags[0])
+            .field("tags", tags[1])
+            .file("attachment", "notes.txt", "text/plain", "some notes")
+            .build()
+    }
+
+    #[test]
+    fn should_match_equal_form() {
+        assert!(FormEquals::new(&upload()).matches(&request(["beach", "sunset"])));
+    }
+
+    #[test]
+    fn should_not_match_different_values() {
+        assert!(!FormEquals::new(&upload()).matches(&request(["sunset", "beach"])));
+        assert!(!FormEquals::new(&Upload { count: 41, ..upload() }).matches(&request(["beach", "sunset"])));
+    }
+
+    #[test]
+    fn should_not_match_extra_or_missing_fields() {
+        let with_description = Upload { description: Some("sunny".into()), ..upload() };
+        assert!(!FormEquals::new(&with_description).matches(&request(["beach", "sunset"])));
+
+        let without_tags = Upload { tags: vec![], ..upload() };
+        assert!(!FormEquals::new(&without_tags).matches(&request(["beach", "sunset"])));
+    }
+
+    #[test]
+    fn should_compare_scalars_by_value_and_strings_exactly() {
+        #[derive(Serialize)]
+        struct Measurement {
+            label: &'static str,
+            value: f64,
+            ratio: f32,
+            count: u32,
+            tags: Vec<&'static str>,
+        }
+
+        let matcher = FormEquals::new(&Measurement { label: "a", value: 1.0, ratio: 0.1, count: 42, tags: vec![] });
+        let request = |label: &str, value: &str, count: &str| {
+            form_request()
+                .field("label", label)
+                .field("value", value)
+                .field("ratio", "0.1")
+                .field("count", count)
+                .build()
+        };
+
+        assert!(matcher.matches(&request("a", "1", "42")));
+        assert!(matcher.matches(&request("a", "1.0", " 42 ")));
+        assert!(matcher.matches(&request("a", "1e0", "+42")));
+        assert!(!matcher.matches(&request("a", "1.5", "42")));
+        assert!(!matcher.matches(&request("a ", "1", "42")));
+        assert!(!matcher.matches(&request("a", "1", "forty-two")));
+    }
+
+    #[test]
+    fn should_reject_nested_structs() {
+        #[derive(Serialize)]
+        struct Outer {
+            inner: Inner,
+        }
+
+        #[derive(Serialize)]
+        struct Inner {
+            value: u32,
+        }
+
+        assert_eq!(
+            FormEquals::try_new(&Outer { inner: Inner { value: 1 } }).unwrap_err().to_string(),
+            "a nested struct cannot be represented as multipart form fields",
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+
+use wiremock::{Match, Request};
+
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+use crate::text::TextNormalization;
+
+/// Matcher for all text fields of a form at once.
+///
+/// Text fields are the parts without a filename; their bodies are decoded as text (see
+/// [`Part::text_with_fallback`](crate::Part::text_with_fallback)) and compared with the expected
+/// values. In `subset()` mode (the default) additional text fields are
+/// allowed, in `exact()` mode they are not. File parts are never considered.
+///
+/// Each expected pair is matched by its own field, so a name listed twice needs two fields. In
+/// `exact()` mode, repeated values of an expected name count as extra fields.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(FormFields::new([
+///             ("first_name", "Jane"),
+///             ("last_name", "Doe"),
+///             ("country", "NZ"),
+///         ]).exact())
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct FormFields {
+    pub fields: Vec<(String, String)>,
+    pub exact: bool,
+    pub text_normalization: TextNormalization,
+}
+
+/// A single difference between the expected and the actual text fields of a form.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FieldMismatch {
+    Missing { name: String, expected: String },
+    Extra { name: String, actual: String },
+    Differs { name: String, expected: String, actual: String },
+}
+
+impl fmt::Display for FieldMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldMismatch::Missing { name, expected } =>
+                write!(f, "missing field `{}`, expected {:?}", name, expected),
+            FieldMismatch::Extra { name, actual } =>
+                write!(f, "unexpected field `{}` with value {:?}", name, actual),
+            FieldMismatch::Differs { name, expected, actual } =>
+                write!(f, "field `{}` is {:?}, expected {:?}", name, actual, expected),
+        }
+    }
+}
+
+impl FormFields {
+    pub fn new<I, K, V>(fields: I) -> Self
+        where I: IntoIterator<Item = (K, V)>,
+              K: Into<String>,
+              V: Into<String>,
+    {
+        FormFields {
+            fields: fields.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+            exact: false,
+            text_normalization: TextNormalization::default(),
+        }
+    }
+
+    /// Don't allow text fields other than the expected ones, including repeated values of an
+    /// expected name.
+    pub fn exact(self) -> Self {
+        FormFields {
+            exact: true,
+            ..self
+        }
+    }
+
+    /// Allow text fields other than the expected ones.
+    pub fn subset(self) -> Self {
+        FormFields {
+            exact: false,
+            ..self
+        }
+    }
+
+    /// Normalize line endings and whitespace before comparing values.
+    pub fn with_text_normalization(self, text_normalization: TextNormalization) -> Self {
+        FormFields {
+            text_normalization,
+            ..self
+        }
+    }
+
+    /// List all missing, extra and differing fields of the request.
+    ///
+    /// The request matches if, and only if, this is empty.
+    pub fn mismatches(&self, request: &Request) -> Vec<FieldMismatch> {
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        let actual = parts.iter()
+            .filter(|part| !part.has_file_input())
+            .filter_map(|part| {
+                let name = part.decoded_name()?;
+                let text = part.text_with_fallback(form_charset)
+                    .unwrap_or_else(|| String::from_utf8_lossy(part.body().unwrap_or_default()));
+                Some((name, text.into_owned()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut matched = vec![false; actual.len()];
+        let mut mismatches = vec![];
+        for (name, expected) in &self.fields {
+            let values = actual.iter()
+                .enumerate()
+                .filter(|(index, (actual_name, _))| !matched[*index] && actual_name == name)
+                .map(|(index, (_, value))| (index, value))
+                .collect::<Vec<_>>();
+
+            let value = values.iter()
+                .find(|(_, value)| self.text_normalization.equals(expected, value))
+                .or_else(|| values.first());
+
+            match value {
+                None => mismatches.push(FieldMismatch::Missing {
+                    name: name.clone(),
+                    expected: expected.clone(),
+                }),
+                Some(&(index, value)) => {
+                    matched[index] = true;
+                    if !self.text_normalization.equals(expected, value) {
+                        mismatches.push(FieldMismatch::Differs {
+                            name: name.clone(),
+                            expected: expected.clone(),
+                            actual: value.clone(),
+                        });
+                    }
+                },
+            }
+        }
+
+        if self.exact {
+            for ((name, value), _) in actual.iter().zip(&matched).filter(|(_, matched)| !**matched) {
+                mismatches.push(FieldMismatch::Extra {
+                    name: name.to_string(),
+                    actual: value.clone(),
+                });
+            }
+        }
+
+        mismatches
+    }
+}
+
+impl Match for FormFields {
+    fn matches(&self, request: &Request) -> bool {
+        request.is_multipart() && self.mismatches(request).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    #[test]
+    fn subset_should_allow_extra_fields() {
+        let request = form_request()
+            .field("first_name", "Jane")
+            .field("last_name", "Doe")
+            .field("country", "NZ")
+            .build();
+
+        assert!(FormFields::new([("first_name", "Jane"), ("country", "NZ")]).matches(&request));
+        assert!(FormFields::new(btreemap!{"last_name" => "Doe"}).subset().matches(&request));
+    }
+
+    #[test]
+    fn exact_should_not_allow_extra_fields() {
+        let request = form_request()
+            .field("first_name", "Jane")
+            .field("last_name", "Doe")
+            .field("country", "NZ")
+            .file("avatar", "me.png", "image/png", "png")
+            .build();
+
+        assert!(!FormFields::new([("first_name", "Jane")]).exact().matches(&request));
+        assert!(FormFields::new([
+            ("first_name", "Jane"),
+            ("last_name", "Doe"),
+            ("country", "NZ"),
+        ]).exact().matches(&request));
+    }
+
+    #[test]
+    fn exact_should_report_repeated_values() {
+        let request = form_request()
+            .field("first_name", "Jane")
+            .field("first_name", "John")
+            .build();
+
+        assert!(FormFields::new([("first_name", "Jane")]).matches(&request));
+        assert_eq!(
+            FormFields::new([("first_name", "Jane")]).exact().mismatches(&request),
+            vec![FieldMismatch::Extra {
+                name: "first_name".into(),
+                actual: "John".into(),
+            }],
+        );
+        assert!(FormFields::new([("first_name", "John"), ("first_name", "Jane")]).exact().matches(&request));
+        assert!(!FormFields::new([("first_name", "Jane"), ("first_name", "Jane")]).matches(&request));
+    }
+
+    #[test]
+    fn should_report_all_mismatches() {
+        let request = form_request()
+            .field("first_name", "Jane")
+            .field("last_name", "Doe")
+            .field("country", "NZ")
+            .file("avatar", "me.png", "image/png", "png")
+            .build();
+
+        assert_eq!(
+            FormFields::new([("first_name", "John"), ("email", "jane@example.com")])
+                .exact()
+                .mismatches(&request),
+            vec![
+                FieldMismatch::Differs {
+                    name: "first_name".into(),
+                    expected: "John".into(),
+                    actual: "Jane".into(),
+                },
+                FieldMismatch::Missing {
+                    name: "email".into(),
+                    expected: "jane@example.com".into(),
+                },
+                FieldMismatch::Extra {
+                    name: "last_name".into(),
+                    actual: "Doe".into(),
+                },
+                FieldMismatch::Extra {
+                    name: "country".into(),
+                    actual: "NZ".into(),
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn should_compare_normalized_values() {
+        let request = form_request().field("comment", "first\r\nsecond").build();
+
+        assert!(!FormFields::new([("comment", "first\nsecond")]).matches(&request));
+        assert!(FormFields::new([("comment", "first\nsecond")])
+            .with_text_normalization(TextNormalization::new().line_endings())
+            .matches(&request));
+    }
+
+    #[test]
+    fn should_display_mismatches() {
+        assert_eq!(
+            FieldMismatch::Differs {
+                name: "first_name".into(),
+                expected: "John".into(),
+                actual: "Jane".into(),
+            }.to_string(),
+            "field `first_name` is \"Jane\", expected \"John\"",
+        );
+    }
+}
@@ -0,0 +1,534 @@
+use std::fmt;
+use std::path::Path;
+
+use base64::Engine;
+use lazy_regex::Regex;
+use serde_json::Value;
+use wiremock::{Match, Request};
+
+use crate::assignment::assign_all;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Matcher for the `multipartPatterns` of a Java WireMock stub mapping.
+///
+/// Every pattern has to be satisfied. A pattern with `matchingType` `ANY` (the default) is
+/// satisfied if at least one part matches its `headers` and `bodyPatterns`, one with `ALL` if
+/// every part does. Supported value patterns are `equalTo` (with `caseInsensitive`), `contains`,
+/// `matches` (which has to match the whole value), `binaryEqualTo`, `equalToJson` (with
+/// `ignoreArrayOrder` and `ignoreExtraElements`) and `absent`. Other patterns and options are
+/// rejected when reading the mapping.
+///
+/// Only available with the `mappings` feature.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     let patterns = MultipartPatterns::from_json_str(r#"{
+///         "request": {
+///             "method": "POST",
+///             "multipartPatterns": [{
+///                 "matchingType": "ANY",
+///                 "headers": {
+///                     "Content-Disposition": { "contains": "name=\"info\"" }
+///                 },
+///                 "bodyPatterns": [{ "equalToJson": { "id": 1 } }]
+///             }]
+///         }
+///     }"#).unwrap();
+///
+///     Mock::given(method("POST"))
+///         .and(patterns)
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartPatterns(pub Vec<MultipartPattern>);
+
+/// A single entry of `multipartPatterns`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartPattern {
+    pub matching_type: MatchingType,
+    pub headers: Vec<(String, ValuePattern)>,
+    pub body_patterns: Vec<ValuePattern>,
+}
+
+/// Whether any or all parts have to match a [`MultipartPattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchingType {
+    #[default]
+    Any,
+    All,
+}
+
+/// A Java WireMock value pattern, applied to a part header or body.
+#[derive(Debug, Clone)]
+pub enum ValuePattern {
+    EqualTo { value: String, case_insensitive: bool },
+    Contains(String),
+    Matches(Regex),
+    BinaryEqualTo(Vec<u8>),
+    EqualToJson { value: Value, ignore_array_order: bool, ignore_extra_elements: bool },
+    Absent,
+}
+
+impl PartialEq for ValuePattern {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                ValuePattern::EqualTo { value: a, case_insensitive: a_case_insensitive },
+                ValuePattern::EqualTo { value: b, case_insensitive: b_case_insensitive },
+            ) => a == b && a_case_insensitive == b_case_insensitive,
+            (ValuePattern::Contains(a), ValuePattern::Contains(b)) => a == b,
+            (ValuePattern::Matches(a), ValuePattern::Matches(b)) => a.as_str() == b.as_str(),
+            (ValuePattern::BinaryEqualTo(a), ValuePattern::BinaryEqualTo(b)) => a == b,
+            (
+                ValuePattern::EqualToJson { value: a, ignore_array_order: a_array_order, ignore_extra_elements: a_extra },
+                ValuePattern::EqualToJson { value: b, ignore_array_order: b_array_order, ignore_extra_elements: b_extra },
+            ) => a == b && a_array_order == b_array_order && a_extra == b_extra,
+            (ValuePattern::Absent, ValuePattern::Absent) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Error returned when a stub mapping cannot be read or uses unsupported patterns.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MappingError(String);
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for MappingError {}
+
+impl MultipartPatterns {
+    /// Read the `multipartPatterns` of a stub mapping file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, MappingError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|error| MappingError(format!("cannot read mapping {}: {}", path.display(), error)))?;
+        Self::from_json_str(&content)
+            .map_err(|error| MappingError(format!("{}: {}", path.display(), error)))
+    }
+
+    /// Parse the `multipartPatterns` of a stub mapping. Accepts a whole stub mapping, its
+    /// `request` object or the `multipartPatterns` array itself.
+    pub fn from_json_str(json: &str) -> Result<Self, MappingError> {
+        let value = serde_json::from_str::<Value>(json)
+            .map_err(|error| MappingError(format!("invalid mapping: {}", error)))?;
+        Self::from_value(&value)
+    }
+
+    /// Like [`MultipartPatterns::from_json_str`], for an already parsed mapping.
+    pub fn from_value(value: &Value) -> Result<Self, MappingError> {
+        let patterns = value.pointer("/request/multipartPatterns")
+            .or_else(|| value.get("multipartPatterns"))
+            .unwrap_or(value);
+
+        patterns.as_array()
+            .ok_or_else(|| MappingError("expected a stub mapping or an array of multipart patterns".to_string()))?
+            .iter()
+            .enumerate()
+            .map(|(index, pattern)| {
+                MultipartPattern::from_value(pattern)
+                    .map_err(|error| MappingError(format!("multipartPatterns[{}]: {}", index, error)))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(MultipartPatterns)
+    }
+}
+
+impl Match for MultipartPatterns {
+    fn matches(&self, request: &Request) -> bool {
+        self.0.iter().all(|pattern| pattern.matches(request))
+    }
+}
+
+impl MultipartPattern {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        let object = value.as_object().ok_or("expected an object")?;
+
+        let matching_type = match object.get("matchingType").map(|value| value.as_str()) {
+            None | Some(Some("ANY")) => MatchingType::Any,
+            Some(Some("ALL")) => MatchingType::All,
+            Some(other) => return Err(format!("unsupported matchingType {}", other.unwrap_or("(not a string)"))),
+        };
+
+        let headers = match object.get("headers") {
+            None => vec![],
+            Some(headers) => headers.as_object()
+                .ok_or("headers must be an object")?
+                .iter()
+                .map(|(name, pattern)| {
+                    ValuePattern::from_value(pattern)
+                        .map(|pattern| (name.clone(), pattern))
+                        .map_err(|error| format!("header {}: {}", name, error))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        let body_patterns = match object.get("bodyPatterns") {
+            None => vec![],
+            Some(patterns) => patterns.as_array()
+                .ok_or("bodyPatterns must be an array")?
+                .iter()
+                .map(|pattern| ValuePattern::from_value(pattern).map_err(|error| format!("bodyPatterns: {}", error)))
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        Ok(MultipartPattern {
+            matching_type,
+            headers,
+            body_patterns,
+        })
+    }
+
+    /// Check a single part against the headers and body patterns.
+    pub fn matches_part(&self, part: &Part) -> bool {
+        let headers = self.headers.iter().all(|(name, pattern)| {
+            let mut values = part.raw_headers()
+                .into_iter()
+                .filter(|(header_name, _)| header_name.eq_ignore_ascii_case(name.as_bytes()))
+                .map(|(_, value)| value);
+
+            match pattern {
+                ValuePattern::Absent => values.next().is_none(),
+                pattern => values.any(|value| pattern.matches_bytes(value)),
+            }
+        });
+
+        let body = part.body().unwrap_or_default();
+        let body_patterns = self.body_patterns.iter().all(|pattern| match pattern {
+            ValuePattern::Absent => body.is_empty(),
+            pattern => pattern.matches_bytes(body),
+        });
+
+        headers && body_patterns
+    }
+}
+
+impl Match for MultipartPattern {
+    fn matches(&self, request: &Request) -> bool {
+        if !request.is_multipart() {
+            return false;
+        }
+
+        let parts = request.parts();
+        match self.matching_type {
+            MatchingType::Any => parts.iter().any(|part| self.matches_part(part)),
+            MatchingType::All => !parts.is_empty() && parts.iter().all(|part| self.matches_part(part)),
+        }
+    }
+}
+
+impl ValuePattern {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        const PATTERNS: [&str; 6] = ["equalTo", "contains", "matches", "binaryEqualTo", "equalToJson", "absent"];
+        const OPTIONS: [&str; 3] = ["caseInsensitive", "ignoreArrayOrder", "ignoreExtraElements"];
+
+        let object = value.as_object().ok_or("expected a pattern object")?;
+        let string = |key: &str| {
+            object[key].as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("{} must be a string", key))
+        };
+        let flag = |key: &str| match object.get(key) {
+            None => Ok(false),
+            Some(Value::Bool(flag)) => Ok(*flag),
+            Some(_) => Err(format!("{} must be a boolean", key)),
+        };
+
+        let mut keys = object.keys().filter(|key| !OPTIONS.contains(&key.as_str()));
+        let key = keys.next().ok_or("empty pattern")?;
+        if let Some(unsupported) = std::iter::once(key).chain(keys.clone()).find(|key| !PATTERNS.contains(&key.as_str())) {
+            return Err(format!("unsupported pattern {}", unsupported));
+        }
+        if let Some(other) = keys.next() {
+            return Err(format!("more than one pattern: {} and {}", key, other));
+        }
+
+        let supported_options: &[&str] = match key.as_str() {
+            "equalTo" => &["caseInsensitive"],
+            "equalToJson" => &["ignoreArrayOrder", "ignoreExtraElements"],
+            _ => &[],
+        };
+        if let Some(option) = object.keys().find(|option| OPTIONS.contains(&option.as_str()) && !supported_options.contains(&option.as_str())) {
+            return Err(format!("{} is not supported for {}", option, key));
+        }
+
+        match key.as_str() {
+            "equalTo" => Ok(ValuePattern::EqualTo { value: string(key)?, case_insensitive: flag("caseInsensitive")? }),
+            "contains" => Ok(ValuePattern::Contains(string(key)?)),
+            // Java WireMock matches the whole value, like `String::matches`.
+            "matches" => Regex::new(&format!("^(?:{})$", string(key)?))
+                .map(ValuePattern::Matches)
+                .map_err(|error| format!("invalid regex: {}", error)),
+            "binaryEqualTo" => base64::engine::general_purpose::STANDARD
+                .decode(string(key)?)
+                .map(ValuePattern::BinaryEqualTo)
+                .map_err(|error| format!("invalid base64: {}", error)),
+            "equalToJson" => Ok(ValuePattern::EqualToJson {
+                value: match &object[key] {
+                    Value::String(json) => serde_json::from_str(json)
+                        .map_err(|error| format!("invalid equalToJson: {}", error))?,
+                    json => json.clone(),
+                },
+                ignore_array_order: flag("ignoreArrayOrder")?,
+                ignore_extra_elements: flag("ignoreExtraElements")?,
+            }),
+            "absent" => Ok(ValuePattern::Absent),
+            other => Err(format!("unsupported pattern {}", other)),
+        }
+    }
+
+    /// Check a header value or body against this pattern. [`ValuePattern::Absent`] never
+    /// matches a present value.
+    pub fn matches_bytes(&self, actual: &[u8]) -> bool {
+        let text = || String::from_utf8_lossy(actual);
+        match self {
+            ValuePattern::EqualTo { value, case_insensitive: false } => actual == value.as_bytes(),
+            ValuePattern::EqualTo { value, case_insensitive: true } => text().to_lowercase() == value.to_lowercase(),
+            ValuePattern::Contains(value) => text().contains(value.as_str()),
+            ValuePattern::Matches(regex) => regex.is_match(&text()),
+            ValuePattern::BinaryEqualTo(bytes) => actual == bytes.as_slice(),
+            ValuePattern::EqualToJson { value, ignore_array_order, ignore_extra_elements } => serde_json::from_slice::<Value>(actual)
+                .map(|actual| json_matches(value, &actual, *ignore_array_order, *ignore_extra_elements))
+                .unwrap_or(false),
+            ValuePattern::Absent => false,
+        }
+    }
+}
+
+/// Compare JSON like Java WireMock's `equalToJson`. Numbers are equal if they have the same
+/// value, e.g. `1` and `1.0`. With `ignore_extra_elements`, objects may have additional members
+/// and arrays additional items. With `ignore_array_order`, every expected item has to match a
+/// different item of the actual array, in any order.
+fn json_matches(expected: &Value, actual: &Value, ignore_array_order: bool, ignore_extra_elements: bool) -> bool {
+    let matches = |expected: &Value, actual: &Value| json_matches(expected, actual, ignore_array_order, ignore_extra_elements);
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            (ignore_extra_elements || expected.len() == actual.len())
+                && expected.iter().all(|(key, expected)| actual.get(key).map(|actual| matches(expected, actual)).unwrap_or(false))
+        },
+        (Value::Array(expected), Value::Array(actual)) => {
+            if expected.len() > actual.len() || (!ignore_extra_elements && expected.len() < actual.len()) {
+                false
+            } else if ignore_array_order {
+                let candidates = expected.iter()
+                    .map(|expected| {
+                        actual.iter()
+                            .enumerate()
+                            .filter(|(_, actual)| matches(expected, actual))
+                            .map(|(index, _)| index)
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                assign_all(&candidates, actual.len())
+            } else {
+                expected.iter().zip(actual).all(|(expected, actual)| matches(expected, actual))
+            }
+        },
+        (Value::Number(expected), Value::Number(actual)) => expected == actual || expected.as_f64() == actual.as_f64(),
+        _ => expected == actual,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    fn patterns(json: &str) -> MultipartPatterns {
+        MultipartPatterns::from_json_str(json).unwrap()
+    }
+
+    #[test]
+    fn should_read_stub_mapping() {
+        let patterns = patterns(r#"{
+            "request": {
+                "urlPath": "/upload",
+                "multipartPatterns": [{
+                    "matchingType": "ALL",
+                    "headers": { "Content-Type": { "absent": true } }
+                }]
+            }
+        }"#);
+
+        assert_eq!(
+            patterns,
+            MultipartPatterns(vec![MultipartPattern {
+                matching_type: MatchingType::All,
+                headers: vec![("Content-Type".into(), ValuePattern::Absent)],
+                body_patterns: vec![],
+            }]),
+        );
+        assert_eq!(self::patterns(r#"{"multipartPatterns": []}"#), MultipartPatterns(vec![]));
+    }
+
+    #[test]
+    fn should_match_header_patterns() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"info\"", "Content-Type: application/json; charset=UTF-8"],
+                r#"{"id": 1, "tags": ["a", "b"]}"#,
+            )
+            .file("file", "hello.txt", "text/plain", "hello")
+            .build();
+
+        assert!(patterns(r#"[{"headers": {"Content-Disposition": {"contains": "name=\"info\""}}}]"#).matches(&request));
+        assert!(patterns(r#"[{"headers": {"content-type": {"equalTo": "TEXT/PLAIN", "caseInsensitive": true}}}]"#).matches(&request));
+        assert!(!patterns(r#"[{"headers": {"Content-Type": {"equalTo": "TEXT/PLAIN"}}}]"#).matches(&request));
+        assert!(patterns(r#"[{"headers": {"Content-Type": {"matches": "application/.*"}}}]"#).matches(&request));
+        assert!(!patterns(r#"[{"headers": {"Content-Type": {"matches": "application/json"}}}]"#).matches(&request));
+        assert!(!patterns(r#"[{"headers": {"Content-Type": {"matches": "json|xml"}}}]"#).matches(&request));
+        assert!(patterns(r#"[{"headers": {"Content-ID": {"absent": true}}}]"#).matches(&request));
+    }
+
+    #[test]
+    fn should_match_body_patterns() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"info\"", "Content-Type: application/json; charset=UTF-8"],
+                r#"{"id": 1, "tags": ["a", "b"]}"#,
+            )
+            .file("file", "hello.txt", "text/plain", "hello")
+            .build();
+
+        assert!(patterns(r#"[{"bodyPatterns": [{"equalToJson": "{\"tags\": [\"a\", \"b\"], \"id\": 1}"}]}]"#).matches(&request));
+        assert!(patterns(r#"[{"bodyPatterns": [{"equalToJson": {"id": 1, "tags": ["a", "b"]}}]}]"#).matches(&request));
+        assert!(!patterns(r#"[{"bodyPatterns": [{"equalToJson": {"id": 2, "tags": ["a", "b"]}}]}]"#).matches(&request));
+        assert!(patterns(r#"[{"bodyPatterns": [{"binaryEqualTo": "aGVsbG8="}]}]"#).matches(&request));
+        assert!(patterns(r#"[{"bodyPatterns": [{"equalTo": "hello"}, {"contains": "ell"}]}]"#).matches(&request));
+        assert!(!patterns(r#"[{"bodyPatterns": [{"equalTo": "hello"}, {"contains": "xyz"}]}]"#).matches(&request));
+    }
+
+    #[test]
+    fn should_apply_equal_to_json_options() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"info\"", "Content-Type: application/json"],
+                r#"{"id": 1, "tags": ["a", "b", "c"], "owner": {"name": "me", "role": "admin"}}"#,
+            )
+            .build();
+        let matches = |pattern: &str| patterns(&format!(r#"[{{"bodyPatterns": [{}]}}]"#, pattern)).matches(&request);
+
+        assert!(!matches(r#"{"equalToJson": {"id": 1, "tags": ["c", "b", "a"], "owner": {"name": "me", "role": "admin"}}}"#));
+        assert!(matches(r#"{"equalToJson": {"id": 1, "tags": ["c", "b", "a"], "owner": {"name": "me", "role": "admin"}}, "ignoreArrayOrder": true}"#));
+        assert!(!matches(r#"{"equalToJson": {"id": 1, "tags": ["a", "a", "b"], "owner": {"name": "me", "role": "admin"}}, "ignoreArrayOrder": true}"#));
+
+        assert!(!matches(r#"{"equalToJson": {"tags": ["a", "b"], "owner": {"name": "me"}}}"#));
+        assert!(matches(r#"{"equalToJson": {"tags": ["a", "b"], "owner": {"name": "me"}}, "ignoreExtraElements": true}"#));
+        assert!(!matches(r#"{"equalToJson": {"tags": ["b", "c"]}, "ignoreExtraElements": true}"#));
+        assert!(!matches(r#"{"equalToJson": {"tags": ["a", "b", "c", "d"]}, "ignoreExtraElements": true}"#));
+        assert!(matches(r#"{"equalToJson": {"tags": ["c", "a"]}, "ignoreArrayOrder": true, "ignoreExtraElements": true}"#));
+        assert!(!matches(r#"{"equalToJson": {"owner": {"name": "you"}}, "ignoreExtraElements": true}"#));
+    }
+
+    #[test]
+    fn should_compare_json_numbers_by_value() {
+        let request = form_request()
+            .part(&["Content-Disposition: form-data; name=\"info\""], r#"{"price": 1.0, "count": 2}"#)
+            .build();
+
+        assert!(patterns(r#"[{"bodyPatterns": [{"equalToJson": {"price": 1, "count": 2.0}}]}]"#).matches(&request));
+        assert!(!patterns(r#"[{"bodyPatterns": [{"equalToJson": {"price": 1.5, "count": 2}}]}]"#).matches(&request));
+    }
+
+    #[test]
+    fn should_match_large_arrays_in_any_order_quickly() {
+        let json_request = |json: String| form_request()
+            .part(&["Content-Disposition: form-data; name=\"info\""], json)
+            .build();
+        let unordered = |expected: String| {
+            patterns(&format!(r#"[{{"bodyPatterns": [{{"equalToJson": {}, "ignoreArrayOrder": true}}]}}]"#, expected))
+        };
+        let array = |values: Vec<u32>| serde_json::to_string(&values).unwrap();
+
+        // Backtracking over interchangeable items takes exponential time, this must not.
+        let ids = json_request(array((0..60).collect()));
+        assert!(unordered(array((0..60).rev().collect())).matches(&ids));
+
+        let zeros = json_request(array(vec![0; 60]));
+        let mut expected = vec![0; 59];
+        expected.push(1);
+        assert!(!unordered(array(expected)).matches(&zeros));
+    }
+
+    #[test]
+    fn should_apply_matching_type() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"info\"", "Content-Type: application/json; charset=UTF-8"],
+                r#"{"id": 1, "tags": ["a", "b"]}"#,
+            )
+            .file("file", "hello.txt", "text/plain", "hello")
+            .build();
+
+        assert!(patterns(r#"[{"matchingType": "ANY", "headers": {"Content-Type": {"equalTo": "text/plain"}}}]"#).matches(&request));
+        assert!(!patterns(r#"[{"matchingType": "ALL", "headers": {"Content-Type": {"equalTo": "text/plain"}}}]"#).matches(&request));
+        assert!(patterns(r#"[{"matchingType": "ALL", "headers": {"Content-Disposition": {"contains": "form-data"}}}]"#).matches(&request));
+    }
+
+    #[test]
+    fn should_require_all_patterns() {
+        let request = form_request()
+            .part(
+                &["Content-Disposition: form-data; name=\"info\"", "Content-Type: application/json; charset=UTF-8"],
+                r#"{"id": 1, "tags": ["a", "b"]}"#,
+            )
+            .file("file", "hello.txt", "text/plain", "hello")
+            .build();
+
+        assert!(patterns(r#"[
+            {"headers": {"Content-Disposition": {"contains": "name=\"info\""}}},
+            {"headers": {"Content-Disposition": {"contains": "name=\"file\""}}}
+        ]"#).matches(&request));
+        assert!(!patterns(r#"[
+            {"headers": {"Content-Disposition": {"contains": "name=\"info\""}}},
+            {"headers": {"Content-Disposition": {"contains": "name=\"other\""}}}
+        ]"#).matches(&request));
+    }
+
+    #[test]
+    fn should_reject_unsupported_patterns() {
+        assert_eq!(
+            MultipartPatterns::from_json_str(r#"[{"bodyPatterns": [{"matchesJsonPath": "$.id"}]}]"#).unwrap_err().to_string(),
+            "multipartPatterns[0]: bodyPatterns: unsupported pattern matchesJsonPath",
+        );
+        assert_eq!(
+            MultipartPatterns::from_json_str(r#"[{"matchingType": "SOME"}]"#).unwrap_err().to_string(),
+            "multipartPatterns[0]: unsupported matchingType SOME",
+        );
+        assert_eq!(
+            MultipartPatterns::from_json_str(r#"[{"bodyPatterns": [{"equalTo": "a", "contains": "b"}]}]"#).unwrap_err().to_string(),
+            "multipartPatterns[0]: bodyPatterns: more than one pattern: contains and equalTo",
+        );
+        assert_eq!(
+            MultipartPatterns::from_json_str(r#"[{"bodyPatterns": [{"equalTo": "a", "ignoreArrayOrder": true}]}]"#).unwrap_err().to_string(),
+            "multipartPatterns[0]: bodyPatterns: ignoreArrayOrder is not supported for equalTo",
+        );
+        assert_eq!(
+            MultipartPatterns::from_json_str(r#"[{"bodyPatterns": [{"equalToJson": {}, "ignoreExtraElements": "yes"}]}]"#).unwrap_err().to_string(),
+            "multipartPatterns[0]: bodyPatterns: ignoreExtraElements must be a boolean",
+        );
+        assert_eq!(
+            MultipartPatterns::from_json_str(r#"[{"bodyPatterns": [{"equalTo": "a", "trim": true}]}]"#).unwrap_err().to_string(),
+            "multipartPatterns[0]: bodyPatterns: unsupported pattern trim",
+        );
+        assert!(MultipartPatterns::from_json_str(r#"{"request": {}}"#).is_err());
+    }
+}
@@ -1,5 +1,41 @@
 pub mod number_of_parts;
 pub mod contains_part;
+pub mod does_not_contain_part;
+pub mod parts_exactly;
+pub mod part_at;
+pub mod all_parts;
+pub mod part_count;
+pub mod form_fields;
+pub mod field_values;
+pub mod empty_file_field;
+pub mod path_traversal_in_filename;
+pub mod part_spec;
+#[cfg(feature = "archive")]
+pub mod archive;
+#[cfg(feature = "serde")]
+pub mod form_equals;
+#[cfg(feature = "fixtures")]
+pub mod fixture;
+#[cfg(feature = "mappings")]
+pub mod mapping;
 
 pub use number_of_parts::NumberOfParts;
-pub use contains_part::ContainsPart;
+pub use contains_part::{ContainsPart, HeaderCondition};
+pub use does_not_contain_part::{DoesNotContainPart, NoPartNamed};
+pub use parts_exactly::PartsExactly;
+pub use part_at::PartAt;
+pub use all_parts::AllParts;
+pub use part_count::PartCount;
+pub use form_fields::{FieldMismatch, FormFields};
+pub use field_values::FieldValues;
+pub use empty_file_field::EmptyFileField;
+pub use path_traversal_in_filename::PathTraversalInFilename;
+pub use part_spec::PartSpec;
+#[cfg(feature = "archive")]
+pub use archive::{archive_contains_entry, ArchivePart};
+#[cfg(feature = "serde")]
+pub use form_equals::FormEquals;
+#[cfg(feature = "fixtures")]
+pub use fixture::{FixtureError, FixturePart, MultipartFixture};
+#[cfg(feature = "mappings")]
+pub use mapping::{MappingError, MatchingType, MultipartPattern, MultipartPatterns, ValuePattern};
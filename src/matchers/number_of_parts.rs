@@ -2,7 +2,7 @@ use wiremock::{Match, Request};
 
 use crate::request_utils::RequestUtils;
 
-/// Match a multipart/form-data request with this many individual parts.
+/// Match a multipart request with this many individual parts.
 pub struct NumberOfParts(pub usize);
 
 impl Match for NumberOfParts {
@@ -12,6 +12,7 @@ impl Match for NumberOfParts {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use indoc::indoc;
     use maplit::hashmap;
@@ -0,0 +1,112 @@
+use wiremock::{Match, Request};
+
+use crate::matchers::ContainsPart;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart request whose part at the given position matches the spec.
+///
+/// Negative indices count from the end, so `-1` is the last part.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(PartAt::first(ContainsPart::new().with_name("key")))
+///         .and(PartAt::last(ContainsPart::new().with_name("file")))
+///         .respond_with(ResponseTemplate::new(204))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct PartAt<'a, 'b, 'c, 'd> {
+    pub index: isize,
+    pub part: ContainsPart<'a, 'b, 'c, 'd>,
+}
+
+impl<'a, 'b, 'c, 'd> PartAt<'a, 'b, 'c, 'd> {
+    pub fn new(index: isize, part: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        PartAt { index, part }
+    }
+
+    pub fn first(part: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        Self::new(0, part)
+    }
+
+    pub fn last(part: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        Self::new(-1, part)
+    }
+}
+
+impl<'a, 'b, 'c, 'd> Match for PartAt<'a, 'b, 'c, 'd> {
+    fn matches(&self, request: &Request) -> bool {
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        let index = if self.index < 0 {
+            parts.len().checked_sub(self.index.unsigned_abs())
+        } else {
+            Some(self.index as usize)
+        };
+
+        index
+            .and_then(|index| parts.get(index))
+            .map(|part| self.part.matches_part_with_charset(part, form_charset))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    #[test]
+    fn should_match_part_at_positive_index() {
+        let request = form_request()
+            .field("key", "uploads/report.pdf")
+            .field("policy", "eyJleHBpcmF0aW9uIjoi")
+            .file("file", "report.pdf", "application/pdf", "%PDF-1.4")
+            .build();
+
+        assert!(PartAt::new(0, ContainsPart::new().with_name("key")).matches(&request));
+        assert!(PartAt::new(1, ContainsPart::new().with_name("policy")).matches(&request));
+        assert!(!PartAt::new(1, ContainsPart::new().with_name("key")).matches(&request));
+        assert!(PartAt::first(ContainsPart::new().with_name("key")).matches(&request));
+    }
+
+    #[test]
+    fn should_match_part_at_negative_index() {
+        let request = form_request()
+            .field("key", "uploads/report.pdf")
+            .field("policy", "eyJleHBpcmF0aW9uIjoi")
+            .file("file", "report.pdf", "application/pdf", "%PDF-1.4")
+            .build();
+
+        assert!(PartAt::new(-1, ContainsPart::new().with_filename("report.pdf")).matches(&request));
+        assert!(PartAt::new(-3, ContainsPart::new().with_name("key")).matches(&request));
+        assert!(!PartAt::new(-2, ContainsPart::new().with_name("file")).matches(&request));
+        assert!(PartAt::last(ContainsPart::new().with_name("file")).matches(&request));
+    }
+
+    #[test]
+    fn should_not_match_out_of_bounds() {
+        let request = form_request()
+            .field("key", "uploads/report.pdf")
+            .field("policy", "eyJleHBpcmF0aW9uIjoi")
+            .file("file", "report.pdf", "application/pdf", "%PDF-1.4")
+            .build();
+
+        assert!(!PartAt::new(3, ContainsPart::new()).matches(&request));
+        assert!(!PartAt::new(-4, ContainsPart::new()).matches(&request));
+    }
+}
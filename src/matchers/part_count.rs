@@ -0,0 +1,84 @@
+use wiremock::{Match, Request};
+
+use crate::matchers::ContainsPart;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart request with a bounded number of parts matching the given criteria.
+///
+/// Usually created with [`ContainsPart::times`] or [`ContainsPart::at_most`].
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(ContainsPart::new().with_name("file").times(2))
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct PartCount<'a, 'b, 'c, 'd> {
+    pub part: ContainsPart<'a, 'b, 'c, 'd>,
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl<'a, 'b, 'c, 'd> Match for PartCount<'a, 'b, 'c, 'd> {
+    fn matches(&self, request: &Request) -> bool {
+        if !request.is_multipart() {
+            return false;
+        }
+
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        let count = parts.iter()
+            .filter(|part| self.part.matches_part_with_charset(part, form_charset))
+            .count();
+
+        count >= self.min && self.max.map(|max| count <= max).unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    #[test]
+    fn times_should_require_exact_count() {
+        let request = form_request()
+            .file("file", "a.txt", "text/plain", "content")
+            .file("file", "b.txt", "text/plain", "content")
+            .field("comment", "content")
+            .build();
+
+        assert!(ContainsPart::new().with_name("file").times(2).matches(&request));
+        assert!(!ContainsPart::new().with_name("file").times(1).matches(&request));
+        assert!(!ContainsPart::new().with_name("file").times(3).matches(&request));
+        assert!(ContainsPart::new().with_name("other").times(0).matches(&request));
+    }
+
+    #[test]
+    fn at_most_should_limit_count() {
+        let request = form_request()
+            .file("file", "a.txt", "text/plain", "content")
+            .file("file", "b.txt", "text/plain", "content")
+            .field("comment", "content")
+            .build();
+
+        assert!(ContainsPart::new().with_body("content".as_bytes()).at_most(3).matches(&request));
+        assert!(!ContainsPart::new().with_body("content".as_bytes()).at_most(2).matches(&request));
+        assert!(ContainsPart::new().with_name("other").at_most(1).matches(&request));
+    }
+}
@@ -0,0 +1,269 @@
+use std::borrow::Cow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use wiremock::{Match, Request};
+
+use crate::matchers::{ContainsPart, HeaderCondition};
+use crate::text::{NameNormalization, TextNormalization};
+
+/// Owned counterpart of [`ContainsPart`] that can be cloned, shared between mocks and, with the
+/// `serde` feature, loaded from configuration.
+///
+/// Absent fields don't restrict the part, just like the unset criteria of [`ContainsPart`].
+/// When (de)serialized, `headers` and `disposition_params` are maps from the name to the
+/// expected value and `body` is a string, or a sequence of bytes if it isn't valid UTF-8.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     let avatar = PartSpec::from(ContainsPart::new()
+///         .with_name("avatar")
+///         .with_content_type("image/png"));
+///
+///     for status in [200, 201] {
+///         Mock::given(method("POST"))
+///             .and(avatar.clone())
+///             .respond_with(ResponseTemplate::new(status))
+///             .mount(&mock_server)
+///             .await;
+///     }
+/// }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default, deny_unknown_fields))]
+#[non_exhaustive]
+pub struct PartSpec {
+    pub name: Option<String>,
+    pub any_name: bool,
+    pub filename: Option<String>,
+    pub any_filename: bool,
+    pub filename_basename: Option<String>,
+    pub content_type: Option<String>,
+    #[cfg_attr(feature = "serde", serde(with = "body_serde"))]
+    pub body: Option<Vec<u8>>,
+    pub max_body_size: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(with = "map_serde"))]
+    pub headers: Vec<(String, HeaderCondition)>,
+    pub disposition: Option<String>,
+    #[cfg_attr(feature = "serde", serde(with = "map_serde"))]
+    pub disposition_params: Vec<(String, String)>,
+    pub text: Option<String>,
+    pub text_normalization: TextNormalization,
+    pub name_normalization: NameNormalization,
+}
+
+impl PartSpec {
+    pub fn new() -> Self { Self::default() }
+
+    /// Borrow this spec as a [`ContainsPart`], e.g. to combine it with [`ContainsPart::times`].
+    pub fn as_contains_part(&self) -> ContainsPart<'_, '_, '_, '_> {
+        ContainsPart {
+            name: self.name.as_deref().map(Cow::Borrowed),
+            any_name: self.any_name,
+            filename: self.filename.as_deref().map(Cow::Borrowed),
+            any_filename: self.any_filename,
+            filename_basename: self.filename_basename.as_deref().map(Cow::Borrowed),
+            content_type: self.content_type.as_deref().map(Cow::Borrowed),
+            body: self.body.as_deref().map(Cow::Borrowed),
+            max_body_size: self.max_body_size,
+            headers: self.headers.iter()
+                .map(|(name, condition)| (Cow::Borrowed(name.as_str()), condition.clone()))
+                .collect(),
+            disposition: self.disposition.as_deref().map(Cow::Borrowed),
+            disposition_params: self.disposition_params.iter()
+                .map(|(name, value)| (Cow::Borrowed(name.as_str()), Cow::Borrowed(value.as_str())))
+                .collect(),
+            text: self.text.as_deref().map(Cow::Borrowed),
+            text_normalization: self.text_normalization,
+            name_normalization: self.name_normalization,
+        }
+    }
+}
+
+impl<'a, 'b, 'c, 'd> From<ContainsPart<'a, 'b, 'c, 'd>> for PartSpec {
+    fn from(part: ContainsPart<'a, 'b, 'c, 'd>) -> Self {
+        PartSpec {
+            name: part.name.map(Cow::into_owned),
+            any_name: part.any_name,
+            filename: part.filename.map(Cow::into_owned),
+            any_filename: part.any_filename,
+            filename_basename: part.filename_basename.map(Cow::into_owned),
+            content_type: part.content_type.map(Cow::into_owned),
+            body: part.body.map(Cow::into_owned),
+            max_body_size: part.max_body_size,
+            headers: part.headers.into_iter()
+                .map(|(name, condition)| (name.into_owned(), condition))
+                .collect(),
+            disposition: part.disposition.map(Cow::into_owned),
+            disposition_params: part.disposition_params.into_iter()
+                .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                .collect(),
+            text: part.text.map(Cow::into_owned),
+            text_normalization: part.text_normalization,
+            name_normalization: part.name_normalization,
+        }
+    }
+}
+
+impl Match for PartSpec {
+    fn matches(&self, request: &Request) -> bool {
+        self.as_contains_part().matches(request)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod body_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Body {
+        Text(String),
+        Bytes(Vec<u8>),
+    }
+
+    pub fn serialize<S: Serializer>(body: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match body.as_deref().map(std::str::from_utf8) {
+            None => serializer.serialize_none(),
+            Some(Ok(text)) => serializer.serialize_str(text),
+            Some(Err(_)) => serializer.collect_seq(body.iter().flatten()),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+        Ok(Option::<Body>::deserialize(deserializer)?.map(|body| match body {
+            Body::Text(text) => text.into_bytes(),
+            Body::Bytes(bytes) => bytes,
+        }))
+    }
+}
+
+/// (De)serializes a list of name-value pairs as a map, keeping their order.
+#[cfg(feature = "serde")]
+mod map_serde {
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, V: Serialize>(pairs: &[(String, V)], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(pairs.iter().map(|(name, value)| (name, value)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, V: Deserialize<'de>>(deserializer: D) -> Result<Vec<(String, V)>, D::Error> {
+        struct PairsVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for PairsVisitor<V> {
+            type Value = Vec<(String, V)>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(pair) = map.next_entry()? {
+                    pairs.push(pair);
+                }
+                Ok(pairs)
+            }
+        }
+
+        deserializer.deserialize_map(PairsVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    #[cfg(feature = "serde")]
+    use lazy_regex::Regex;
+
+    use crate::test_utils::{multipart_header, requestb};
+
+    use super::*;
+
+    fn contains_part() -> ContainsPart<'static, 'static, 'static, 'static> {
+        ContainsPart::new()
+            .with_name("avatar")
+            .with_filename("me.png")
+            .with_content_type("image/png")
+            .with_body("png".as_bytes())
+            .with_header_matching("Content-ID", "^<.+>$")
+            .with_text_normalization(TextNormalization::new().line_endings())
+    }
+
+    #[test]
+    fn should_convert_to_and_from_contains_part() {
+        let spec = PartSpec::from(contains_part());
+
+        assert_eq!(spec.name.as_deref(), Some("avatar"));
+        assert_eq!(spec.body.as_deref(), Some("png".as_bytes()));
+        assert_eq!(spec.as_contains_part(), contains_part());
+    }
+
+    #[test]
+    fn should_match_like_contains_part() {
+        let request = requestb(
+            multipart_header(),
+            indoc!{r#"
+            --xyz
+            Content-Disposition: form-data; name="avatar"; filename="me.png"
+            Content-Type: image/png
+            Content-ID: <avatar@example.com>
+
+            png
+            --xyz--
+        "#}.as_bytes().into()
+        );
+
+        let spec = PartSpec::from(contains_part());
+        assert!(spec.matches(&request));
+        assert!(spec.as_contains_part().times(1).matches(&request));
+        assert!(!PartSpec { name: Some("other".into()), ..spec }.matches(&request));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let spec = PartSpec {
+            name: Some("avatar".into()),
+            headers: vec![
+                ("Content-ID".into(), HeaderCondition::Matches(Regex::new("^<.+>$").unwrap())),
+                ("Content-Transfer-Encoding".into(), HeaderCondition::Absent),
+            ],
+            name_normalization: NameNormalization::new().nfc(),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&spec).unwrap();
+        assert_eq!(json["name"], "avatar");
+        assert_eq!(json["headers"], serde_json::json!({
+            "Content-ID": {"matches": "^<.+>$"},
+            "Content-Transfer-Encoding": "absent",
+        }));
+        assert_eq!(serde_json::from_value::<PartSpec>(json).unwrap(), spec);
+
+        for body in [b"png".to_vec(), vec![0x89, b'P', b'N', b'G']] {
+            let spec = PartSpec { body: Some(body), ..Default::default() };
+            assert_eq!(serde_json::from_value::<PartSpec>(serde_json::to_value(&spec).unwrap()).unwrap(), spec);
+        }
+        assert_eq!(serde_json::to_value(PartSpec { body: Some(b"png".to_vec()), ..Default::default() }).unwrap()["body"], "png");
+
+        assert_eq!(
+            serde_json::from_str::<PartSpec>(r#"{"filename": "me.png"}"#).unwrap(),
+            PartSpec { filename: Some("me.png".into()), ..Default::default() },
+        );
+        assert!(serde_json::from_str::<PartSpec>(r#"{"headers": [["X", {"matches": "("}]]}"#).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use wiremock::{Match, Request};
+
+use crate::assignment::assign_all;
+use crate::matchers::ContainsPart;
+use crate::part::Part;
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart request whose parts correspond one-to-one to the given specs.
+///
+/// Every spec has to match a different part and every part has to be matched by a spec. By
+/// default the order of the parts doesn't matter; specs are assigned to parts so that
+/// overlapping specs still find a complete matching if one exists. Use `in_order()` to require
+/// the n-th spec to match the n-th part.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(PartsExactly::new(vec![
+///             ContainsPart::new().with_name("metadata"),
+///             ContainsPart::new().with_name("file").with_content_type("image/png"),
+///         ]).in_order())
+///         .respond_with(ResponseTemplate::new(200))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct PartsExactly<'a, 'b, 'c, 'd> {
+    pub parts: Vec<ContainsPart<'a, 'b, 'c, 'd>>,
+    pub ordered: bool,
+}
+
+impl<'a, 'b, 'c, 'd> PartsExactly<'a, 'b, 'c, 'd> {
+    pub fn new<T: IntoIterator<Item = ContainsPart<'a, 'b, 'c, 'd>>>(parts: T) -> Self {
+        PartsExactly {
+            parts: parts.into_iter().collect(),
+            ordered: false,
+        }
+    }
+
+    pub fn in_order(self) -> Self {
+        PartsExactly {
+            ordered: true,
+            ..self
+        }
+    }
+
+    pub fn any_order(self) -> Self {
+        PartsExactly {
+            ordered: false,
+            ..self
+        }
+    }
+}
+
+impl<'a, 'b, 'c, 'd> Match for PartsExactly<'a, 'b, 'c, 'd> {
+    fn matches(&self, request: &Request) -> bool {
+        if !request.is_multipart() {
+            return false;
+        }
+
+        let parts = request.parts();
+        let form_charset = Part::form_charset(&parts);
+        if parts.len() != self.parts.len() {
+            return false;
+        }
+
+        if self.ordered {
+            return self.parts.iter()
+                .zip(parts.iter())
+                .all(|(spec, part)| spec.matches_part_with_charset(part, form_charset));
+        }
+
+        let candidates = self.parts.iter()
+            .map(|spec| {
+                parts.iter()
+                    .enumerate()
+                    .filter(|(_, part)| spec.matches_part_with_charset(part, form_charset))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assign_all(&candidates, parts.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    #[test]
+    fn should_require_same_number_of_parts() {
+        let request = form_request()
+            .file("file", "a.txt", "text/plain", "text")
+            .file("file", "b.png", "image/png", "png")
+            .build();
+
+        assert!(!PartsExactly::new(vec![ContainsPart::new().with_name("file")]).matches(&request));
+        assert!(!PartsExactly::new((0..3).map(|_| ContainsPart::new())).matches(&request));
+    }
+
+    #[test]
+    fn should_not_match_one_part_with_two_specs() {
+        let request = form_request()
+            .file("file", "a.txt", "text/plain", "text")
+            .file("file", "b.png", "image/png", "png")
+            .build();
+
+        assert!(!PartsExactly::new(vec![
+            ContainsPart::new().with_filename("a.txt"),
+            ContainsPart::new().with_content_type("text/plain"),
+        ]).matches(&request));
+    }
+
+    #[test]
+    fn should_find_assignment_for_overlapping_specs() {
+        let request = form_request()
+            .file("file", "a.txt", "text/plain", "text")
+            .file("file", "b.png", "image/png", "png")
+            .build();
+
+        assert!(PartsExactly::new(vec![
+            ContainsPart::new().with_name("file"),
+            ContainsPart::new().with_filename("a.txt"),
+        ]).matches(&request));
+    }
+
+    #[test]
+    fn should_respect_order_in_order_mode() {
+        let request = form_request()
+            .file("file", "a.txt", "text/plain", "text")
+            .file("file", "b.png", "image/png", "png")
+            .build();
+        let specs = || vec![
+            ContainsPart::new().with_filename("b.png"),
+            ContainsPart::new().with_filename("a.txt"),
+        ];
+
+        assert!(PartsExactly::new(specs()).any_order().matches(&request));
+        assert!(!PartsExactly::new(specs()).in_order().matches(&request));
+        assert!(PartsExactly::new(specs().into_iter().rev()).in_order().matches(&request));
+    }
+}
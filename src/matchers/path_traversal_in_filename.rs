@@ -0,0 +1,66 @@
+use wiremock::{Match, Request};
+
+use crate::request_utils::RequestUtils;
+
+/// Match a multipart request with a filename that tries to escape the upload
+/// directory, e.g. `../../etc/passwd` or `..\..\boot.ini`.
+///
+/// Only `..` path segments count as traversal. Absolute paths such as
+/// `C:\Users\me\Desktop\report.pdf`, which old Internet Explorer versions send for regular
+/// uploads, don't match; use [`Part::basename`](crate::Part::basename) to compare those.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock, ResponseTemplate};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::prelude::*;
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .and(PathTraversalInFilename)
+///         .respond_with(ResponseTemplate::new(400))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct PathTraversalInFilename;
+
+impl Match for PathTraversalInFilename {
+    fn matches(&self, request: &Request) -> bool {
+        request.parts().iter()
+            .filter_map(|part| part.decoded_filename())
+            .any(|filename| filename.split(['/', '\\']).any(|segment| segment == ".."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::form_request;
+
+    use super::*;
+
+    fn request(filename: &str) -> Request {
+        form_request().file("file", filename, "text/plain", "content").build()
+    }
+
+    #[test]
+    fn should_match_parent_directory_segments() {
+        assert!(PathTraversalInFilename.matches(&request("../tmp/x.txt")));
+        assert!(PathTraversalInFilename.matches(&request("uploads/../../etc/passwd")));
+        assert!(PathTraversalInFilename.matches(&request(r"..\..\boot.ini")));
+        assert!(PathTraversalInFilename.matches(&request("..")));
+    }
+
+    #[test]
+    fn should_not_match_regular_filenames() {
+        assert!(!PathTraversalInFilename.matches(&request("report.pdf")));
+        assert!(!PathTraversalInFilename.matches(&request("report..pdf")));
+        assert!(!PathTraversalInFilename.matches(&request(r"C:\Users\me\Desktop\report.pdf")));
+        assert!(!PathTraversalInFilename.matches(&request("")));
+    }
+}
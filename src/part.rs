@@ -1,4 +1,8 @@
-use lazy_regex::regex;
+use std::borrow::Cow;
+
+use crate::content_range::ContentRange;
+use crate::request_utils::{parse_multipart_content_type, split_parts};
+use crate::text;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Part<'a> {
@@ -6,75 +10,334 @@ pub struct Part<'a> {
 }
 
 impl<'a> Part<'a> {
+    /// The `name` parameter of the `Content-Disposition` header as it was sent, see
+    /// [`Part::decoded_name`] for the name a server would see.
     pub fn name(&self) -> Option<&'a str> {
-        let header = self.header();
-        match header {
-            None => None,
-            Some(header) => {
-                let regex = regex!(r#";\s*name="([^"].*?)""#i);
-                regex.captures(header)
-                    .and_then(|cap| cap.get(1))
-                    .map(|mtch| mtch.as_str())
-            },
-        }
+        self.disposition_param("name")
     }
 
+    /// The `name` parameter of the `Content-Disposition` header, decoded.
+    ///
+    /// Names that are not valid UTF-8 are decoded as ISO-8859-1. Backslash escapes (`\"` and `\\`) and the percent-encoding browsers use for `"`, CR and LF
+    /// (`%22`, `%0D`, `%0A`) are decoded.
+    pub fn decoded_name(&self) -> Option<Cow<'a, str>> {
+        self.raw_disposition_param("name").map(decode_param)
+    }
+
+    /// The `filename` parameter of the `Content-Disposition` header as it was sent, see
+    /// [`Part::decoded_filename`].
     pub fn filename(&self) -> Option<&'a str> {
-        let header = self.header();
-        match header {
-            None => None,
-            Some(header) => {
-                let regex = regex!(r#";\s*filename="([^"].*?)""#i);
-                regex.captures(header)
-                    .and_then(|cap| cap.get(1))
-                    .map(|mtch| mtch.as_str())
-            },
-        }
+        self.disposition_param("filename")
+    }
+
+    /// The `filename` parameter of the `Content-Disposition` header, decoded like
+    /// [`Part::decoded_name`].
+    pub fn decoded_filename(&self) -> Option<Cow<'a, str>> {
+        self.raw_disposition_param("filename").map(decode_param)
+    }
+
+    /// The decoded filename without any Windows or POSIX directory components, e.g.
+    /// `report.pdf` for `C:\Users\me\Desktop\report.pdf` as sent by old versions of Internet
+    /// Explorer.
+    pub fn basename(&self) -> Option<Cow<'a, str>> {
+        self.decoded_filename().map(|filename| match filename {
+            Cow::Borrowed(filename) => Cow::Borrowed(basename(filename)),
+            Cow::Owned(filename) => Cow::Owned(basename(&filename).to_string()),
+        })
     }
 
+    /// Whether the part was sent by a file input, i.e. has a `filename` parameter. This is also
+    /// the case if no file was chosen, in which case browsers send an empty filename.
+    pub fn has_file_input(&self) -> bool {
+        self.raw_disposition_param("filename").is_some()
+    }
+
+    /// Whether the part was sent by a file input for which no file was chosen: the filename
+    /// is empty and so is the body.
+    pub fn is_empty_file_input(&self) -> bool {
+        self.raw_disposition_param("filename") == Some(b"") && self.body().map(|body| body.is_empty()).unwrap_or(true)
+    }
+
+    /// The value of the `Content-Type` header.
     pub fn content_type(&self) -> Option<&'a str> {
-        let header = self.header();
-        match header {
-            None => None,
-            Some(header) => {
-                let regex = regex!(r#"content-type:\s*([^\n].*)"#i);
-                regex.captures(header)
-                    .and_then(|cap| cap.get(1))
-                    .map(|mtch| mtch.as_str())
-            },
-        }
+        self.header_value("content-type")
+    }
+
+    /// All headers of the part as name/value pairs, in order of appearance.
+    ///
+    /// Headers that are not valid UTF-8 are decoded as ISO-8859-1, see [`Part::raw_headers`] for
+    /// the bytes as they were sent.
+    pub fn headers(&self) -> Vec<(Cow<'a, str>, Cow<'a, str>)> {
+        self.raw_headers()
+            .into_iter()
+            .map(|(name, value)| (text::utf8_or_latin1(name), text::utf8_or_latin1(value)))
+            .collect()
+    }
+
+    /// All headers of the part as name/value pairs of bytes, in order of appearance.
+    pub fn raw_headers(&self) -> Vec<(&'a [u8], &'a [u8])> {
+        self.raw_header()
+            .split(|byte| *byte == b'\n')
+            .filter_map(|line| {
+                let colon = line.iter().position(|byte| *byte == b':')?;
+                Some((line[..colon].trim_ascii(), line[colon + 1..].trim_ascii()))
+            })
+            .collect()
     }
 
+    /// The bytes of the first header with the given name, compared case-insensitively.
+    pub fn raw_header_value(&self, name: &str) -> Option<&'a [u8]> {
+        self.raw_headers()
+            .into_iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name.as_bytes()))
+            .map(|(_, value)| value)
+    }
+
+    /// The value of the first header with the given name, compared case-insensitively. `None`
+    /// if the value is not valid UTF-8.
+    pub fn header_value(&self, name: &str) -> Option<&'a str> {
+        self.raw_header_value(name)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// The disposition type of the `Content-Disposition` header, e.g. `form-data`, `attachment`
+    /// or `inline`.
+    pub fn disposition_type(&self) -> Option<&'a str> {
+        self.raw_header_value("content-disposition")
+            .and_then(|value| split_params(value).into_iter().next())
+            .filter(|disposition_type| !disposition_type.is_empty())
+            .and_then(|disposition_type| std::str::from_utf8(disposition_type).ok())
+    }
+
+    /// The value of a `Content-Disposition` parameter, with surrounding quotes removed.
+    /// Parameter names are compared case-insensitively. `None` if the value is not valid UTF-8,
+    /// see [`Part::raw_disposition_param`] for those.
+    pub fn disposition_param(&self, name: &str) -> Option<&'a str> {
+        self.raw_disposition_param(name)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// The bytes of a `Content-Disposition` parameter, with surrounding quotes removed.
+    ///
+    /// The header is parsed byte by byte, so parameters are found even if another parameter,
+    /// e.g. a Latin-1 filename, is not valid UTF-8.
+    pub fn raw_disposition_param(&self, name: &str) -> Option<&'a [u8]> {
+        self.raw_header_value("content-disposition")
+            .and_then(|value| header_param(value, name))
+    }
+
+    /// The `Content-Range` header of a `multipart/byteranges` part.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        self.header_value("content-range")
+            .and_then(ContentRange::parse)
+    }
+
+    /// The `charset` parameter of the `Content-Type` header.
+    pub fn charset(&self) -> Option<&'a str> {
+        self.header_value("content-type")
+            .and_then(|value| header_param(value.as_bytes(), "charset"))
+            .and_then(|charset| std::str::from_utf8(charset).ok())
+    }
+
+    /// The body decoded as text, using the charset of the part or UTF-8.
+    ///
+    /// Charsets other than UTF-8 and US-ASCII require the `encoding` feature. Returns `None` if
+    /// the body cannot be decoded.
+    pub fn text(&self) -> Option<Cow<'a, str>> {
+        self.text_with_fallback(None)
+    }
+
+    /// The body decoded as text, using the charset of the part, the given fallback charset
+    /// (usually the form's `_charset_` field, see
+    /// [`RequestUtils::form_charset`](crate::RequestUtils::form_charset)) or UTF-8, in that order.
+    pub fn text_with_fallback(&self, fallback_charset: Option<&str>) -> Option<Cow<'a, str>> {
+        let charset = self.charset()
+            .or(fallback_charset)
+            .unwrap_or("utf-8");
+        text::decode(self.body()?, charset)
+    }
+
+    /// The header section of the part. It is empty for parts without headers and `None` if it
+    /// is not valid UTF-8.
     pub fn header(&self) -> Option<&'a str> {
-        match self.header_body_boundary() {
-            None => None,
-            Some((end_of_header_index, _)) => {
-                Some(std::str::from_utf8(&self.content[0..end_of_header_index]).unwrap())
-            },
-        }
+        std::str::from_utf8(self.raw_header()).ok()
+    }
+
+    /// The bytes of the header section of the part.
+    pub fn raw_header(&self) -> &'a [u8] {
+        let (end_of_header_index, _) = self.header_body_boundary();
+        &self.content[0..end_of_header_index]
     }
 
+    /// The header section of the part, with invalid UTF-8 sequences replaced by `U+FFFD`.
+    pub fn header_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.raw_header())
+    }
+
+    /// The header section of the part decoded as ISO-8859-1, as sent by some older clients
+    /// for non-ASCII filenames.
+    pub fn header_latin1(&self) -> Cow<'a, str> {
+        text::latin1(self.raw_header())
+    }
+
+    /// The body of the part. It is empty for parts that consist only of headers.
     pub fn body(&self) -> Option<&'a [u8]> {
-        match self.header_body_boundary() {
-            None => None,
-            Some((end_of_header_index, separator_len)) => {
-                Some(&self.content[(end_of_header_index + separator_len)..])
-            },
+        let (end_of_header_index, separator_len) = self.header_body_boundary();
+        Some(&self.content[(end_of_header_index + separator_len)..])
+    }
+
+    /// The parts of a nested multipart body, e.g. a `multipart/mixed` part of a form. Empty if
+    /// the part is not a multipart with a boundary.
+    pub fn nested_parts(&self) -> Vec<Part<'a>> {
+        self.content_type()
+            .and_then(parse_multipart_content_type)
+            .and_then(|content_type| content_type.boundary)
+            .map(|boundary| split_parts(self.body().unwrap_or_default(), boundary))
+            .unwrap_or_default()
+    }
+
+    /// The charset announced by the `_charset_` field among `parts`, if any.
+    pub fn form_charset(parts: &[Part<'a>]) -> Option<&'a str> {
+        parts.iter()
+            .find(|part| part.name() == Some("_charset_"))
+            .and_then(|part| part.body())
+            .and_then(|body| std::str::from_utf8(body).ok())
+            .map(|charset| charset.trim())
+    }
+
+    /// Group parts by their decoded name, preserving the order of first appearance and the
+    /// order of parts within a group. Parts without a name are skipped.
+    pub fn group_by_name<'p>(parts: &'p [Part<'a>]) -> Vec<(Cow<'a, str>, Vec<&'p Part<'a>>)> {
+        let mut groups: Vec<(Cow<'a, str>, Vec<&'p Part<'a>>)> = vec![];
+        for part in parts {
+            if let Some(name) = part.decoded_name() {
+                match groups.iter_mut().find(|(group_name, _)| *group_name == name) {
+                    Some((_, group)) => group.push(part),
+                    None => groups.push((name, vec![part])),
+                }
+            }
         }
+        groups
     }
 
-    fn header_body_boundary(&self) -> Option<(usize, usize)> {
-        self.content
-            .windows(4)
-            .enumerate()
-            .find_map(|(index, window)| {
-                match window {
-                    [b'\n', b'\n', _, _] => Some((index, 2)),
-                    [b'\r', b'\n', b'\r', b'\n'] => Some((index, 4)),
-                    _ => None,
+    /// Index of the end of the header section and length of the separator that follows it.
+    ///
+    /// A part starting with a line break has no headers. A part without a blank line consists
+    /// only of headers, which were terminated by the following delimiter.
+    fn header_body_boundary(&self) -> (usize, usize) {
+        let content = self.content;
+        if content.starts_with(b"\r\n") {
+            return (0, 2);
+        }
+        if content.starts_with(b"\n") {
+            return (0, 1);
+        }
+
+        let blank_line = (0..content.len())
+            .find_map(|index| {
+                let rest = &content[index..];
+                if rest.starts_with(b"\r\n\r\n") {
+                    Some((index, 4))
+                } else if rest.starts_with(b"\n\n") {
+                    Some((index, 2))
+                } else {
+                    None
                 }
-            })
+            });
+
+        blank_line.unwrap_or_else(|| {
+            if content.ends_with(b"\r\n") {
+                (content.len() - 2, 2)
+            } else if content.ends_with(b"\n") {
+                (content.len() - 1, 1)
+            } else {
+                (content.len(), 0)
+            }
+        })
+    }
+}
+
+/// Split a header value at semicolons that are not inside a quoted string.
+///
+/// Works on bytes, as all delimiters are ASCII and values may be in any ASCII-compatible charset.
+pub(crate) fn split_params(value: &[u8]) -> Vec<&[u8]> {
+    let mut params = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, byte) in value.iter().enumerate() {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' if quoted => escaped = true,
+            b'"' => quoted = !quoted,
+            b';' if !quoted => {
+                params.push(value[start..index].trim_ascii());
+                start = index + 1;
+            },
+            _ => {},
+        }
     }
+    params.push(value[start..].trim_ascii());
+    params
+}
+
+/// Find a parameter of a header value like `form-data; name="field"`, with quotes removed.
+/// Parameter names are compared case-insensitively.
+pub(crate) fn header_param<'h>(value: &'h [u8], name: &str) -> Option<&'h [u8]> {
+    split_params(value)
+        .into_iter()
+        .skip(1)
+        .filter_map(|param| param.iter().position(|byte| *byte == b'=').map(|equals| param.split_at(equals)))
+        .find(|(param_name, _)| param_name.trim_ascii().eq_ignore_ascii_case(name.as_bytes()))
+        .map(|(_, value)| unquote(value[1..].trim_ascii()))
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Decode the backslash escapes `\"` and `\\` and the WHATWG percent-encoding of `"`, CR and LF.
+///
+/// Other backslashes are kept, as old Internet Explorer versions send unescaped Windows paths.
+/// Values that are not valid UTF-8 are decoded as ISO-8859-1 first.
+fn decode_param(raw: &[u8]) -> Cow<'_, str> {
+    let raw = match text::utf8_or_latin1(raw) {
+        Cow::Borrowed(raw) => raw,
+        Cow::Owned(latin1) => return Cow::Owned(unescape(&latin1).into_owned()),
+    };
+    unescape(raw)
+}
+
+fn unescape(raw: &str) -> Cow<'_, str> {
+    if !raw.contains(['\\', '%']) {
+        return Cow::Borrowed(raw);
+    }
+
+    let mut decoded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(c) = rest.chars().next() {
+        let percent_encoded = [("%22", '"'), ("%0D", '\r'), ("%0A", '\n')]
+            .iter()
+            .find(|(encoded, _)| rest.get(..3).map(|prefix| prefix.eq_ignore_ascii_case(encoded)).unwrap_or(false));
+
+        if let Some((_, replacement)) = percent_encoded {
+            decoded.push(*replacement);
+            rest = &rest[3..];
+        } else if rest.starts_with("\\\"") || rest.starts_with("\\\\") {
+            decoded.push(rest.as_bytes()[1] as char);
+            rest = &rest[2..];
+        } else {
+            decoded.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    Cow::Owned(decoded)
+}
+
+fn unquote(value: &[u8]) -> &[u8] {
+    value.strip_prefix(b"\"")
+        .and_then(|value| value.strip_suffix(b"\""))
+        .unwrap_or(value)
 }
 
 impl<'a> From<&'a [u8]> for Part<'a> {
@@ -135,6 +398,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_extract_short_body() {
+        let part = Part::from("Content-Disposition: form-data; name=\"a\"\n\nx");
+
+        assert_eq!(part.name(), Some("a"));
+        assert_eq!(part.body(), Some("x".as_bytes()));
+    }
+
+    #[test]
+    fn should_extract_empty_body() {
+        for content in ["Content-Type: text/plain\r\n\r\n", "Content-Type: text/plain\n\n"] {
+            let part = Part::from(content);
+
+            assert_eq!(part.header(), Some("Content-Type: text/plain"));
+            assert_eq!(part.body(), Some("".as_bytes()));
+        }
+    }
+
+    #[test]
+    fn should_extract_part_without_headers() {
+        for content in ["\r\nbody\r\nwith lines", "\nbody\nwith lines"] {
+            let part = Part::from(content);
+
+            assert_eq!(part.header(), Some(""));
+            assert_eq!(part.headers(), vec![]);
+            assert_eq!(part.body(), Some(content.trim_start().as_bytes()));
+        }
+    }
+
+    #[test]
+    fn should_extract_part_with_headers_only() {
+        for content in ["Content-Disposition: form-data; name=\"a\"", "Content-Disposition: form-data; name=\"a\"\r\n"] {
+            let part = Part::from(content);
+
+            assert_eq!(part.header(), Some("Content-Disposition: form-data; name=\"a\""));
+            assert_eq!(part.name(), Some("a"));
+            assert_eq!(part.body(), Some("".as_bytes()));
+        }
+    }
+
+    #[test]
+    fn should_extract_empty_part() {
+        let part = Part::from("");
+
+        assert_eq!(part.header(), Some(""));
+        assert_eq!(part.body(), Some("".as_bytes()));
+    }
+
     #[test]
     fn should_extract_part_name() {
         assert_eq!(
@@ -151,6 +462,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_extract_empty_name_and_filename() {
+        let part = Part::from("Content-Disposition: form-data; name=\"\"; filename=\"\"\nContent-Type: application/octet-stream\n\n");
+
+        assert_eq!(part.name(), Some(""));
+        assert_eq!(part.filename(), Some(""));
+    }
+
+    #[test]
+    fn should_decode_escaped_quotes_in_name_and_filename() {
+        let part = Part::from(r#"Content-Disposition: form-data; name="say \"hi\""; filename="a\\b.txt"
+
+content"#);
+
+        assert_eq!(part.decoded_name().as_deref(), Some("say \"hi\""));
+        assert_eq!(part.name(), Some(r#"say \"hi\""#));
+        assert_eq!(part.decoded_filename().as_deref(), Some("a\\b.txt"));
+        assert_eq!(part.filename(), Some(r#"a\\b.txt"#));
+    }
+
+    #[test]
+    fn should_decode_percent_encoded_quotes_and_newlines_in_name_and_filename() {
+        let part = Part::from("Content-Disposition: form-data; name=\"say %22hi%22\"; filename=\"a%0d%0Ab%25.txt\"\n\ncontent");
+
+        assert_eq!(part.decoded_name().as_deref(), Some("say \"hi\""));
+        assert_eq!(part.name(), Some("say %22hi%22"));
+        assert_eq!(part.decoded_filename().as_deref(), Some("a\r\nb%25.txt"));
+    }
+
+    #[test]
+    fn should_not_take_name_from_filename() {
+        let part = Part::from("Content-Disposition: form-data; filename=\"x; name=\\\"y\\\"\"\n\ncontent");
+
+        assert_eq!(part.name(), None);
+        assert_eq!(part.decoded_filename().as_deref(), Some("x; name=\"y\""));
+    }
+
+    #[test]
+    fn should_strip_directories_from_filename() {
+        for (filename, basename) in [
+            (r#"C:\Users\me\Desktop\report.pdf"#, "report.pdf"),
+            ("../tmp/x.txt", "x.txt"),
+            ("/etc/passwd", "passwd"),
+            ("report.pdf", "report.pdf"),
+            ("dir/", ""),
+        ] {
+            let content = format!("Content-Disposition: form-data; name=\"file\"; filename=\"{}\"\n\ncontent", filename);
+            assert_eq!(Part::from(&content).basename().as_deref(), Some(basename));
+        }
+
+        assert_eq!(Part::from("Content-Disposition: form-data; name=\"text\"\n\ncontent").basename(), None);
+    }
+
+    #[test]
+    fn should_not_panic_on_headers_that_are_not_utf8() {
+        let part = Part::from(&b"Content-Disposition: form-data; name=\"file\"; filename=\"caf\xe9.txt\"\r\nContent-Type: text/plain\r\n\r\ncontent"[..]);
+
+        assert_eq!(part.header(), None);
+        assert_eq!(part.raw_header(), &b"Content-Disposition: form-data; name=\"file\"; filename=\"caf\xe9.txt\"\r\nContent-Type: text/plain"[..]);
+        assert_eq!(part.header_lossy(), "Content-Disposition: form-data; name=\"file\"; filename=\"caf\u{FFFD}.txt\"\r\nContent-Type: text/plain");
+        assert_eq!(part.header_latin1(), "Content-Disposition: form-data; name=\"file\"; filename=\"café.txt\"\r\nContent-Type: text/plain");
+
+        assert_eq!(part.name(), Some("file"));
+        assert_eq!(part.decoded_name(), Some("file".into()));
+        assert_eq!(part.filename(), None);
+        assert_eq!(part.raw_disposition_param("filename"), Some(&b"caf\xe9.txt"[..]));
+        assert_eq!(part.decoded_filename(), Some("café.txt".into()));
+        assert_eq!(part.basename(), Some("café.txt".into()));
+        assert!(part.has_file_input());
+        assert_eq!(part.disposition_type(), Some("form-data"));
+        assert_eq!(part.content_type(), Some("text/plain"));
+        assert_eq!(part.header_value("content-disposition"), None);
+        assert_eq!(
+            part.headers(),
+            vec![
+                (Cow::from("Content-Disposition"), Cow::from("form-data; name=\"file\"; filename=\"café.txt\"")),
+                (Cow::from("Content-Type"), Cow::from("text/plain")),
+            ],
+        );
+        assert_eq!(
+            part.raw_header_value("content-disposition"),
+            Some(&b"form-data; name=\"file\"; filename=\"caf\xe9.txt\""[..]),
+        );
+        assert_eq!(part.body(), Some("content".as_bytes()));
+    }
+
+    #[test]
+    fn should_detect_file_inputs() {
+        let text = Part::from("Content-Disposition: form-data; name=\"text\"\n\ncontent");
+        let file = Part::from("Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\n\ncontent");
+        let empty_file = Part::from("Content-Disposition: form-data; name=\"file\"; filename=\"\"\r\nContent-Type: application/octet-stream\r\n\r\n");
+
+        assert!(!text.has_file_input());
+        assert!(file.has_file_input());
+        assert!(empty_file.has_file_input());
+
+        assert!(!text.is_empty_file_input());
+        assert!(!file.is_empty_file_input());
+        assert!(empty_file.is_empty_file_input());
+    }
+
     #[test]
     fn should_extract_content_type() {
         assert_eq!(
@@ -159,6 +571,96 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_extract_headers() {
+        let part = Part::from("Content-Disposition: form-data; name=\"text\"\r\nContent-ID: <part1@example.com>\r\nX-Checksum:abc\r\n\r\ncontent");
+
+        assert_eq!(
+            part.headers(),
+            vec![
+                (Cow::from("Content-Disposition"), Cow::from("form-data; name=\"text\"")),
+                (Cow::from("Content-ID"), Cow::from("<part1@example.com>")),
+                (Cow::from("X-Checksum"), Cow::from("abc")),
+            ],
+        );
+        assert_eq!(part.header_value("content-id"), Some("<part1@example.com>"));
+        assert_eq!(part.header_value("x-checksum"), Some("abc"));
+        assert_eq!(part.header_value("Content-Length"), None);
+    }
+
+    #[test]
+    fn should_extract_disposition_type_and_params() {
+        let part = Part::from("Content-Disposition: attachment; filename=\"a;b.txt\"; Creation-Date=\"Wed, 12 Feb 1997 16:29:51 -0500\"; size=42\n\ncontent");
+
+        assert_eq!(part.disposition_type(), Some("attachment"));
+        assert_eq!(part.disposition_param("filename"), Some("a;b.txt"));
+        assert_eq!(part.disposition_param("creation-date"), Some("Wed, 12 Feb 1997 16:29:51 -0500"));
+        assert_eq!(part.disposition_param("size"), Some("42"));
+        assert_eq!(part.disposition_param("modification-date"), None);
+    }
+
+    #[test]
+    fn should_return_none_without_content_disposition() {
+        let part = Part::from("Content-Type: text/plain\n\ncontent");
+
+        assert_eq!(part.disposition_type(), None);
+        assert_eq!(part.disposition_param("name"), None);
+    }
+
+    #[test]
+    fn should_decode_text_as_utf8_by_default() {
+        let part = Part::from("Content-Disposition: form-data; name=\"text\"\n\ngrüße");
+
+        assert_eq!(part.charset(), None);
+        assert_eq!(part.text().as_deref(), Some("grüße"));
+    }
+
+    #[test]
+    fn should_not_decode_invalid_text() {
+        let content = b"Content-Type: text/plain\n\n\xfc".to_vec();
+
+        assert_eq!(Part::from(content.as_slice()).text(), None);
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn should_decode_text_with_part_charset() {
+        let content = b"Content-Type: text/plain; charset=windows-1252\n\ngr\xfc\xdfe".to_vec();
+        let part = Part::from(content.as_slice());
+
+        assert_eq!(part.charset(), Some("windows-1252"));
+        assert_eq!(part.text().as_deref(), Some("grüße"));
+        assert_eq!(part.text_with_fallback(Some("Shift_JIS")).as_deref(), Some("grüße"));
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn should_decode_text_with_fallback_charset() {
+        let content = b"Content-Disposition: form-data; name=\"text\"\n\n\x93\xfa\x96\x7b".to_vec();
+        let part = Part::from(content.as_slice());
+
+        assert_eq!(part.text(), None);
+        assert_eq!(part.text_with_fallback(Some("Shift_JIS")).as_deref(), Some("日本"));
+    }
+
+    #[test]
+    fn should_group_parts_by_name() {
+        let parts = vec![
+            Part::from("Content-Disposition: form-data; name=\"tags\"\n\nfirst"),
+            Part::from("Content-Disposition: form-data; name=\"title\"\n\ntitle"),
+            Part::from("Content-Type: text/plain\n\nno name"),
+            Part::from("Content-Disposition: form-data; name=\"tags\"\n\nsecond"),
+        ];
+
+        assert_eq!(
+            Part::group_by_name(&parts),
+            vec![
+                ("tags".into(), vec![&parts[0], &parts[3]]),
+                ("title".into(), vec![&parts[1]]),
+            ],
+        );
+    }
+
     #[test]
     fn should_extract_part_body() {
         assert_eq!(
@@ -1,20 +1,50 @@
 use std::str::FromStr;
 
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
 use wiremock::http::HeaderName;
 use wiremock::Request;
 
-use crate::part::Part;
+#[cfg(feature = "serde")]
+use crate::form::{self, FormError};
+use crate::part::{header_param, split_params, Part};
 
 pub trait RequestUtils {
-    fn multipart_contenttype(&self) -> Option<MultipartContentType>;
-    fn parts(&self) -> Vec<Part>;
+    fn multipart_contenttype(&self) -> Option<MultipartContentType<'_>>;
+    fn parts(&self) -> Vec<Part<'_>>;
+
+    /// Whether the request is a multipart request with a boundary.
+    fn is_multipart(&self) -> bool {
+        self.multipart_contenttype()
+            .map(|content_type| content_type.boundary.is_some())
+            .unwrap_or(false)
+    }
+
+    /// The charset announced by the form's `_charset_` field, if any.
+    fn form_charset(&self) -> Option<&str> {
+        Part::form_charset(&self.parts())
+    }
+
+    /// Deserialize the form fields of a multipart request into `T`.
+    ///
+    /// Text parts are decoded with their charset or the form's `_charset_` field and map to
+    /// strings, numbers, booleans and unit enum variants, file parts to
+    /// [`UploadedFile`](crate::UploadedFile). Repeated names can be collected into a `Vec`,
+    /// absent names into an `Option`.
+    #[cfg(feature = "serde")]
+    fn form<T: DeserializeOwned>(&self) -> Result<T, FormError> {
+        if !self.is_multipart() {
+            return Err(serde::de::Error::custom("not a multipart request"));
+        }
+        form::from_parts(&self.parts())
+    }
 }
 
 impl RequestUtils for Request {
-    fn multipart_contenttype(&self) -> Option<MultipartContentType> {
+    fn multipart_contenttype(&self) -> Option<MultipartContentType<'_>> {
         let content_type = self
             .headers
-            .get_all(&HeaderName::from_str("content-type").unwrap())
+            .get_all(HeaderName::from_str("content-type").unwrap())
             .iter()
             .find(|value| {
                 value
@@ -23,103 +53,73 @@ impl RequestUtils for Request {
                     .to_lowercase()
                     .starts_with("multipart/")
             });
-        match content_type {
-            None => None,
-            Some(value) => {
-                let parts = value
-                    .to_str()
-                    .unwrap_or_default()
-                    .split(";")
-                    .collect::<Vec<_>>();
-
-                let multipart_type = parts[0].split("/").nth(1).unwrap().trim();
-
-                let boundary = parts
-                    .iter()
-                    .map(|part| part.trim())
-                    .find(|part| part.starts_with("boundary="))
-                    .map(|whole| whole.split("=").nth(1).unwrap().trim());
-
-                Some(MultipartContentType {
-                    multipart_type,
-                    boundary,
-                })
-            }
-        }
+        content_type
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_multipart_content_type)
     }
 
-    fn parts(&self) -> Vec<Part> {
-        if let Some(content_type) = self.multipart_contenttype() {
-            if content_type.multipart_type == "form-data" {
-                if let Some(boundary) = content_type.boundary {
-                    let boundary = {
-                        let mut tmp: Vec<u8> = vec!['-' as u8; boundary.as_bytes().len() + 2];
-                        tmp[0] = '-' as u8;
-                        tmp[1] = '-' as u8;
-                        tmp[2..].copy_from_slice(boundary.as_bytes());
-                        tmp
-                    };
-
-                    let boundary_start_indexes = self
-                        .body
-                        .windows(boundary.len())
-                        .enumerate()
-                        .filter(|(_, window)| window == &boundary)
-                        .map(|(index, _)| index)
-                        .collect::<Vec<_>>();
-
-                    boundary_start_indexes
-                        .windows(2)
-                        .map(|w| (boundary.len() + 1 + w[0], w[1]))
-                        .map(|(start, end)| &self.body[start..end])
-                        .map(|body| trim_single_linebreak_from_start(body))
-                        .map(|body| trim_single_linebreak_from_end(body))
-                        .map(|it| Part::from(it))
-                        .collect::<Vec<_>>()
-                } else {
-                    vec![]
-                }
-            } else {
-                vec![]
-            }
-        } else {
-            vec![]
+    fn parts(&self) -> Vec<Part<'_>> {
+        match self.multipart_contenttype().and_then(|content_type| content_type.boundary) {
+            Some(boundary) => split_parts(&self.body, boundary),
+            None => vec![],
         }
     }
 }
 
-fn trim_single_linebreak_from_start(body: &[u8]) -> &[u8] {
-    if body.len() >= 2 {
-        match body[..2] {
-            [b'\r', b'\n'] => &body[2..],
-            [b'\n', _] => &body[1..],
-            _ => body,
-        }
-    } else if body.len() >= 1 {
-        match body[0] {
-            b'\n' => &body[1..],
-            _ => body,
-        }
-    } else {
-        body
+/// Parse a `multipart/*` content type into its subtype and boundary.
+pub(crate) fn parse_multipart_content_type(value: &str) -> Option<MultipartContentType<'_>> {
+    let media_type = split_params(value.as_bytes()).into_iter().next()?;
+    let (media_type, multipart_type) = std::str::from_utf8(media_type).ok()?.split_once("/")?;
+    if !media_type.eq_ignore_ascii_case("multipart") {
+        return None;
     }
+
+    let boundary = header_param(value.as_bytes(), "boundary")
+        .and_then(|boundary| std::str::from_utf8(boundary).ok());
+
+    Some(MultipartContentType {
+        multipart_type: multipart_type.trim(),
+        boundary,
+    })
+}
+
+/// Split a multipart body into its parts, ignoring the preamble and the epilogue.
+pub(crate) fn split_parts<'a>(body: &'a [u8], boundary: &str) -> Vec<Part<'a>> {
+    let boundary = {
+        let mut tmp: Vec<u8> = vec![b'-'; boundary.len() + 2];
+        tmp[0] = b'-';
+        tmp[1] = b'-';
+        tmp[2..].copy_from_slice(boundary.as_bytes());
+        tmp
+    };
+
+    let boundary_start_indexes = body
+        .windows(boundary.len())
+        .enumerate()
+        .filter(|(_, window)| window == &boundary)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    boundary_start_indexes
+        .windows(2)
+        .map(|w| (boundary.len() + w[0], w[1]))
+        .map(|(start, end)| &body[start..end])
+        .map(trim_single_linebreak_from_start)
+        .map(trim_single_linebreak_from_end)
+        .map(Part::from)
+        .collect::<Vec<_>>()
+}
+
+fn trim_single_linebreak_from_start(body: &[u8]) -> &[u8] {
+    body.strip_prefix(b"\r\n")
+        .or_else(|| body.strip_prefix(b"\n"))
+        .unwrap_or(body)
 }
 
 fn trim_single_linebreak_from_end(body: &[u8]) -> &[u8] {
-    if body.len() >= 2 {
-        match body[(body.len() - 2)..(body.len())] {
-            [b'\r', b'\n'] => &body[..body.len() - 2],
-            [_, b'\n'] => &body[..body.len() - 1],
-            _ => body,
-        }
-    } else if body.len() >= 1 {
-        match body[(body.len() - 1)..(body.len())] {
-            [_, b'\n'] => &body[..body.len() - 1],
-            _ => body,
-        }
-    } else {
-        body
-    }
+    body.strip_suffix(b"\r\n")
+        .or_else(|| body.strip_suffix(b"\n"))
+        .unwrap_or(body)
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -183,6 +183,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multipart_contenttype_should_read_boundary_containing_equals_sign() {
+        assert_eq!(
+            request(hashmap! {
+                name("content-type") => values("multipart/mixed; boundary=----=_Part_0_1234.5678"),
+            },)
+            .multipart_contenttype(),
+            Some(MultipartContentType {
+                multipart_type: "mixed",
+                boundary: Some("----=_Part_0_1234.5678"),
+            })
+        );
+    }
+
+    #[test]
+    fn multipart_contenttype_should_unquote_boundary() {
+        assert_eq!(
+            request(hashmap! {
+                name("content-type") => values("multipart/related; type=\"application/xop+xml\"; Boundary=\"uuid:0ca0e16e=feb1\"; start=\"<root>\""),
+            },)
+            .multipart_contenttype(),
+            Some(MultipartContentType {
+                multipart_type: "related",
+                boundary: Some("uuid:0ca0e16e=feb1"),
+            })
+        );
+    }
+
+    #[test]
+    fn multipart_contenttype_should_ignore_boundary_inside_quoted_param() {
+        assert_eq!(
+            request(hashmap! {
+                name("content-type") => values("multipart/related; type=\"text/xml; boundary=evil\"; boundary=xyz"),
+            },)
+            .multipart_contenttype(),
+            Some(MultipartContentType {
+                multipart_type: "related",
+                boundary: Some("xyz"),
+            })
+        );
+    }
+
+    #[test]
+    fn is_multipart_should_require_boundary() {
+        assert!(request(multipart_header()).is_multipart());
+
+        assert!(!request(hashmap! {
+            name("content-type") => values("multipart/form-data"),
+        }).is_multipart());
+
+        assert!(request(hashmap! {
+            name("content-type") => values("multipart/mixed; boundary=xyz"),
+        }).is_multipart());
+
+        assert!(!request(hashmap! {
+            name("content-type") => values("application/json"),
+        }).is_multipart());
+    }
+
+    #[test]
+    fn form_charset_should_read_charset_field() {
+        assert_eq!(
+            requestb(
+                multipart_header(),
+                indoc! {r#"
+                    --xyz
+                    Content-Disposition: form-data; name="_charset_"
+
+                    windows-1252
+                    --xyz--
+                "#}
+                .as_bytes()
+                .into(),
+            )
+            .form_charset(),
+            Some("windows-1252"),
+        );
+
+        assert_eq!(request(multipart_header()).form_charset(), None);
+    }
+
+    #[test]
+    fn parts_should_keep_parts_without_headers_or_body() {
+        let body = "--xyz\r\n\r\nno headers\r\n--xyz\r\nContent-Type: text/plain\r\n--xyz\r\nContent-Type: text/plain\r\n\r\n\r\n--xyz--\r\n";
+        let request = requestb(
+            hashmap! {
+                name("content-type") => values("multipart/mixed; boundary=xyz"),
+            },
+            body.as_bytes().into(),
+        );
+        let parts = request.parts();
+
+        assert_eq!(
+            parts.iter().map(|part| (part.header(), part.body())).collect::<Vec<_>>(),
+            vec![
+                (Some(""), Some("no headers".as_bytes())),
+                (Some("Content-Type: text/plain"), Some("".as_bytes())),
+                (Some("Content-Type: text/plain"), Some("".as_bytes())),
+            ],
+        );
+    }
+
+    #[test]
+    fn parts_should_keep_parts_without_headers_with_newlines() {
+        let request = requestb(
+            multipart_header(),
+            "--xyz\n\nno headers\n--xyz--\n".as_bytes().into(),
+        );
+        let parts = request.parts();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].header(), Some(""));
+        assert_eq!(parts[0].body(), Some("no headers".as_bytes()));
+    }
+
+    #[test]
+    fn parts_should_support_other_multipart_types() {
+        assert_eq!(
+            requestb(
+                hashmap! {
+                    name("content-type") => values("multipart/mixed; boundary=xyz"),
+                },
+                indoc! {"
+                    --xyz
+                    Content-Disposition: attachment; filename=\"a.txt\"
+
+                    content
+                    --xyz--
+                "}
+                .as_bytes()
+                .into(),
+            )
+            .parts(),
+            vec![Part::from(
+                "Content-Disposition: attachment; filename=\"a.txt\"\n\ncontent"
+            ),],
+        );
+    }
+
     #[test]
     fn parts_should_find_single_text_part() {
         assert_eq!(
@@ -0,0 +1,144 @@
+use wiremock::{Request, Respond, ResponseTemplate};
+
+use crate::builder::{MultipartBuilder, PartBuilder};
+
+/// Responder that answers with a multipart body, e.g. for batch APIs, MTOM or file bundle
+/// downloads.
+///
+/// The body is built with [`MultipartBuilder`] for every response, so unless a boundary is
+/// set on the builder, each response gets a fresh one. The `Content-Type` header carries the
+/// boundary and the parameters of the builder. Responses can be parsed back with
+/// [`MultipartBody::parts`](crate::MultipartBody::parts).
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock::{MockServer, Mock};
+/// use wiremock::matchers::method;
+/// use wiremock_multipart::{MultipartResponseTemplate, PartBuilder};
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let mock_server = MockServer::start().await;
+///
+///     Mock::given(method("POST"))
+///         .respond_with(MultipartResponseTemplate::new(200)
+///             .part(PartBuilder::new()
+///                 .with_content_type("application/http")
+///                 .with_header("Content-ID", "<response-1>")
+///                 .with_body("HTTP/1.1 204 No Content\r\n\r\n")))
+///         .mount(&mock_server)
+///         .await;
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartResponseTemplate {
+    pub status: u16,
+    pub multipart: MultipartBuilder,
+    pub headers: Vec<(String, String)>,
+}
+
+impl MultipartResponseTemplate {
+    /// A `multipart/mixed` response with the given status code.
+    pub fn new(status: u16) -> Self {
+        MultipartResponseTemplate {
+            status,
+            multipart: MultipartBuilder::mixed(),
+            headers: vec![],
+        }
+    }
+
+    /// Respond with the given multipart, e.g. a `multipart/related` one.
+    pub fn with_multipart(self, multipart: MultipartBuilder) -> Self {
+        MultipartResponseTemplate {
+            multipart,
+            ..self
+        }
+    }
+
+    pub fn part(self, part: PartBuilder) -> Self {
+        MultipartResponseTemplate {
+            multipart: self.multipart.part(part),
+            ..self
+        }
+    }
+
+    /// Add a response header.
+    pub fn with_header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Build the body and turn it into a plain [`ResponseTemplate`].
+    pub fn template(&self) -> ResponseTemplate {
+        let multipart = self.multipart.build();
+        self.headers.iter()
+            .fold(ResponseTemplate::new(self.status), |template, (name, value)| {
+                template.insert_header(name.as_str(), value.as_str())
+            })
+            .set_body_raw(multipart.body, &multipart.content_type)
+    }
+}
+
+impl Respond for MultipartResponseTemplate {
+    fn respond(&self, _request: &Request) -> ResponseTemplate {
+        self.template()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{Mock, MockServer};
+    use wiremock::matchers::method;
+
+    use crate::builder::MultipartBody;
+    use crate::test_utils::{get, header};
+
+    use super::*;
+
+    #[async_std::test]
+    async fn should_respond_with_multipart_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(MultipartResponseTemplate::new(207)
+                .with_header("X-Batch-Id", "42")
+                .part(PartBuilder::new().with_header("Content-ID", "<1>").with_body("first"))
+                .part(PartBuilder::new().with_content_type("application/json").with_body("{}")))
+            .mount(&server)
+            .await;
+
+        let (status, headers, body) = get(&server, &[]);
+        let content_type = header(&headers, "content-type").unwrap().to_string();
+
+        assert_eq!(status, 207);
+        assert_eq!(header(&headers, "x-batch-id"), Some("42"));
+        assert!(content_type.starts_with("multipart/mixed; boundary="));
+
+        let multipart = MultipartBody { content_type, body };
+        let parts = multipart.parts();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].header_value("content-id"), Some("<1>"));
+        assert_eq!(parts[0].body(), Some("first".as_bytes()));
+        assert_eq!(parts[1].content_type(), Some("application/json"));
+    }
+
+    #[async_std::test]
+    async fn should_respond_with_related_multipart() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(MultipartResponseTemplate::new(200).with_multipart(MultipartBuilder::related()
+                .with_boundary("mime-boundary")
+                .with_param("type", "application/xop+xml")
+                .part(PartBuilder::new().with_content_type("application/xop+xml").with_body("<soap/>"))))
+            .mount(&server)
+            .await;
+
+        let (_, headers, body) = get(&server, &[]);
+
+        assert_eq!(
+            header(&headers, "content-type"),
+            Some("multipart/related; boundary=mime-boundary; type=\"application/xop+xml\""),
+        );
+        assert!(body.starts_with(b"--mime-boundary\r\nContent-Type: application/xop+xml\r\n\r\n<soap/>\r\n"));
+    }
+}
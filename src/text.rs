@@ -0,0 +1,301 @@
+use std::borrow::Cow;
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Decode `bytes` using the charset with the given label.
+///
+/// UTF-8 and US-ASCII are always supported, other charsets require the `encoding` feature.
+/// Returns `None` for unknown charsets and for bytes that are invalid in the charset.
+pub(crate) fn decode<'a>(bytes: &'a [u8], charset: &str) -> Option<Cow<'a, str>> {
+    let charset = charset.trim().trim_matches('"');
+    if ["utf-8", "utf8", "us-ascii"].iter().any(|utf8| charset.eq_ignore_ascii_case(utf8)) {
+        std::str::from_utf8(bytes).ok().map(Cow::Borrowed)
+    } else {
+        decode_other(bytes, charset)
+    }
+}
+
+#[cfg(feature = "encoding")]
+fn decode_other<'a>(bytes: &'a [u8], charset: &str) -> Option<Cow<'a, str>> {
+    encoding_rs::Encoding::for_label(charset.as_bytes())?
+        .decode_without_bom_handling_and_without_replacement(bytes)
+}
+
+#[cfg(not(feature = "encoding"))]
+fn decode_other<'a>(_bytes: &'a [u8], _charset: &str) -> Option<Cow<'a, str>> {
+    None
+}
+
+/// Decode `bytes` as ISO-8859-1, which maps every byte to the code point of the same value.
+pub(crate) fn latin1(bytes: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(ascii) if bytes.is_ascii() => Cow::Borrowed(ascii),
+        _ => Cow::Owned(bytes.iter().map(|byte| char::from(*byte)).collect()),
+    }
+}
+
+/// Decode header bytes as UTF-8, or as ISO-8859-1 if they are not valid UTF-8, as sent by some
+/// older clients.
+pub(crate) fn utf8_or_latin1(bytes: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => latin1(bytes),
+    }
+}
+
+/// Normalization applied to both the expected and the actual text before comparing them.
+///
+/// All normalizations are off by default, so texts have to be identical.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock_multipart::TextNormalization;
+///
+/// let normalization = TextNormalization::new()
+///     .line_endings()
+///     .trim_trailing_whitespace();
+///
+/// assert!(normalization.equals("first line  \r\nsecond line\r\n", "first line\nsecond line"));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct TextNormalization {
+    /// Treat `\r\n` and `\r` like `\n`.
+    pub line_endings: bool,
+    /// Ignore whitespace at the end of each line and at the end of the text. As `\r` counts as
+    /// whitespace, this also ignores the difference between `\r\n` and `\n`.
+    pub trim_trailing_whitespace: bool,
+    /// Treat every run of whitespace, including line breaks, like a single space and ignore
+    /// leading and trailing whitespace.
+    pub collapse_whitespace: bool,
+}
+
+impl TextNormalization {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn line_endings(self) -> Self {
+        TextNormalization {
+            line_endings: true,
+            ..self
+        }
+    }
+
+    pub fn trim_trailing_whitespace(self) -> Self {
+        TextNormalization {
+            trim_trailing_whitespace: true,
+            ..self
+        }
+    }
+
+    pub fn collapse_whitespace(self) -> Self {
+        TextNormalization {
+            collapse_whitespace: true,
+            ..self
+        }
+    }
+
+    /// Apply the normalizations to `text`.
+    pub fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let mut text = Cow::Borrowed(text);
+
+        if self.line_endings && text.contains('\r') {
+            text = Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"));
+        }
+
+        if self.trim_trailing_whitespace {
+            let trimmed = text.split('\n')
+                .map(|line| line.trim_end())
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim_end()
+                .to_string();
+            if trimmed != text {
+                text = Cow::Owned(trimmed);
+            }
+        }
+
+        if self.collapse_whitespace {
+            let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if collapsed != text {
+                text = Cow::Owned(collapsed);
+            }
+        }
+
+        text
+    }
+
+    /// Compare two texts after normalizing both.
+    pub fn equals(&self, left: &str, right: &str) -> bool {
+        self.apply(left) == self.apply(right)
+    }
+}
+
+/// Unicode normalization form, see [`NameNormalization::nfc`] and [`NameNormalization::nfd`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum UnicodeForm {
+    Nfc,
+    Nfd,
+}
+
+/// Normalization applied to both the expected and the actual part name or filename before
+/// comparing them.
+///
+/// All normalizations are off by default, so names have to be identical.
+///
+/// ## Example
+///
+/// ```rust
+/// use wiremock_multipart::NameNormalization;
+///
+/// let normalization = NameNormalization::new()
+///     .ignore_ascii_case()
+///     .nfc();
+///
+/// assert!(normalization.equals("Café.txt", "cafe\u{301}.TXT"));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct NameNormalization {
+    /// Compare ASCII letters case-insensitively, e.g. for clients that title-case field names.
+    pub ignore_ascii_case: bool,
+    /// Bring both names into the same Unicode normalization form, e.g. for macOS clients that
+    /// send filenames in NFD.
+    pub unicode_form: Option<UnicodeForm>,
+}
+
+impl NameNormalization {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn ignore_ascii_case(self) -> Self {
+        NameNormalization {
+            ignore_ascii_case: true,
+            ..self
+        }
+    }
+
+    pub fn nfc(self) -> Self {
+        NameNormalization {
+            unicode_form: Some(UnicodeForm::Nfc),
+            ..self
+        }
+    }
+
+    pub fn nfd(self) -> Self {
+        NameNormalization {
+            unicode_form: Some(UnicodeForm::Nfd),
+            ..self
+        }
+    }
+
+    /// Apply the normalizations to `name`.
+    pub fn apply<'t>(&self, name: &'t str) -> Cow<'t, str> {
+        let mut name = Cow::Borrowed(name);
+
+        if let Some(form) = self.unicode_form {
+            let normalized: String = match form {
+                UnicodeForm::Nfc => name.nfc().collect(),
+                UnicodeForm::Nfd => name.nfd().collect(),
+            };
+            if normalized != name {
+                name = Cow::Owned(normalized);
+            }
+        }
+
+        if self.ignore_ascii_case && name.bytes().any(|byte| byte.is_ascii_uppercase()) {
+            name = Cow::Owned(name.to_ascii_lowercase());
+        }
+
+        name
+    }
+
+    /// Compare two names after normalizing both.
+    pub fn equals(&self, left: &str, right: &str) -> bool {
+        self.apply(left) == self.apply(right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_should_compare_exactly() {
+        assert!(TextNormalization::new().equals("a\r\nb", "a\r\nb"));
+        assert!(!TextNormalization::new().equals("a\r\nb", "a\nb"));
+        assert!(!TextNormalization::new().equals("a ", "a"));
+    }
+
+    #[test]
+    fn should_normalize_line_endings() {
+        let normalization = TextNormalization::new().line_endings();
+
+        assert_eq!(normalization.apply("a\r\nb\rc\n"), "a\nb\nc\n");
+        assert!(normalization.equals("a\r\nb", "a\nb"));
+        assert!(!normalization.equals("a \r\nb", "a\nb"));
+    }
+
+    #[test]
+    fn should_trim_trailing_whitespace() {
+        let normalization = TextNormalization::new().trim_trailing_whitespace();
+
+        assert_eq!(normalization.apply("a  \n  b\t\n\n"), "a\n  b");
+        assert!(normalization.equals("a \nb\n", "a\nb"));
+        assert!(!normalization.equals(" a\nb", "a\nb"));
+    }
+
+    #[test]
+    fn should_collapse_whitespace() {
+        let normalization = TextNormalization::new().collapse_whitespace();
+
+        assert_eq!(normalization.apply("  a \r\n\t b  "), "a b");
+        assert!(normalization.equals("first\r\n  second", "first second"));
+        assert!(!normalization.equals("first second", "firstsecond"));
+    }
+
+    #[test]
+    fn default_should_compare_names_exactly() {
+        assert!(NameNormalization::new().equals("café", "café"));
+        assert!(!NameNormalization::new().equals("café", "cafe\u{301}"));
+        assert!(!NameNormalization::new().equals("Name", "name"));
+    }
+
+    #[test]
+    fn should_ignore_ascii_case_of_names() {
+        let normalization = NameNormalization::new().ignore_ascii_case();
+
+        assert_eq!(normalization.apply("First_Name"), "first_name");
+        assert!(normalization.equals("First_Name", "first_name"));
+        assert!(!normalization.equals("É", "é"));
+    }
+
+    #[test]
+    fn should_normalize_unicode_form_of_names() {
+        assert_eq!(NameNormalization::new().nfc().apply("cafe\u{301}"), "café");
+        assert_eq!(NameNormalization::new().nfd().apply("café"), "cafe\u{301}");
+        assert!(NameNormalization::new().nfc().equals("café.txt", "cafe\u{301}.txt"));
+        assert!(NameNormalization::new().nfd().equals("café.txt", "cafe\u{301}.txt"));
+    }
+
+    #[test]
+    fn should_decode_utf8() {
+        assert_eq!(decode("grüße".as_bytes(), "UTF-8"), Some(Cow::Borrowed("grüße")));
+        assert_eq!(decode("plain".as_bytes(), "\"us-ascii\""), Some(Cow::Borrowed("plain")));
+        assert_eq!(decode(&[0x67, 0xfc], "utf-8"), None);
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn should_decode_other_charsets() {
+        assert_eq!(decode(&[0x67, 0x72, 0xfc, 0xdf, 0x65], "windows-1252").as_deref(), Some("grüße"));
+        assert_eq!(decode(&[0x93, 0xfa, 0x96, 0x7b], "Shift_JIS").as_deref(), Some("日本"));
+        assert_eq!(decode(b"text", "no-such-charset"), None);
+    }
+
+    #[cfg(not(feature = "encoding"))]
+    #[test]
+    fn should_not_decode_other_charsets_without_feature() {
+        assert_eq!(decode(b"text", "windows-1252"), None);
+    }
+}